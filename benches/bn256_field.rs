@@ -45,6 +45,19 @@ pub fn bench_bn256_field(c: &mut Criterion) {
     group.bench_function("bn256_fq_legendre", |bencher| {
         bencher.iter(|| black_box(&a).legendre())
     });
+
+    let a = Fq12::random(&mut rng);
+    let b = Fq12::random(&mut rng);
+
+    group.bench_function("bn256_fq12_mul", |bencher| {
+        bencher.iter(|| black_box(&a).mul(black_box(&b)))
+    });
+    group.bench_function("bn256_fq12_mul_schoolbook", |bencher| {
+        bencher.iter(|| black_box(&a).mul_schoolbook(black_box(&b)))
+    });
+    group.bench_function("bn256_fq12_mul_lazy", |bencher| {
+        bencher.iter(|| black_box(&a).mul_lazy(black_box(&b)))
+    });
 }
 
 criterion_group!(benches, bench_bn256_field);
@@ -0,0 +1,41 @@
+//! Compares `G1::fixed_base_batch_mul` against one scalar multiplication per
+//! scalar, to locate the batch size from which the shared table pays off.
+//!
+//! To run this benchmark:
+//!
+//!     cargo bench --bench bn256_fixed_base
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use halo2curves::bn256::{Fr, G1Affine, G1};
+use halo2curves::ff::Field;
+use halo2curves::group::{Curve, Group};
+use rand::SeedableRng;
+use rand_xorshift::XorShiftRng;
+
+fn bench_fixed_base(c: &mut Criterion) {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+    let base: G1Affine = G1::random(&mut rng).to_affine();
+
+    let mut group = c.benchmark_group("BN256 G1 fixed base");
+    for n in [1, 2, 4, 8, 16, 64] {
+        let scalars = (0..n).map(|_| Fr::random(&mut rng)).collect::<Vec<_>>();
+        group.bench_with_input(BenchmarkId::new("batch", n), &scalars, |b, scalars| {
+            b.iter(|| G1::fixed_base_batch_mul(black_box(&base), black_box(scalars)))
+        });
+        group.bench_with_input(BenchmarkId::new("individual", n), &scalars, |b, scalars| {
+            b.iter(|| {
+                black_box(scalars)
+                    .iter()
+                    .map(|k| black_box(base) * k)
+                    .collect::<Vec<G1>>()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_fixed_base);
+criterion_main!(benches);
@@ -0,0 +1,555 @@
+use crate::arithmetic::{adc, mac, sbb};
+use crate::ff::{FromUniformBytes, PrimeField, WithSmallOrderMulGroup};
+use crate::{
+    extend_field_legendre, field_arithmetic_6_limbs, field_bits_6_limbs, field_common_6_limbs,
+    impl_from_u64_6_limbs,
+};
+use crate::{
+    impl_add_binop_specify_output, impl_binops_additive, impl_binops_additive_specify_output,
+    impl_binops_multiplicative, impl_binops_multiplicative_mixed, impl_sub_binop_specify_output,
+    impl_sum_prod,
+};
+use core::convert::TryInto;
+use core::fmt;
+use core::ops::{Add, Mul, Neg, Sub};
+use rand::RngCore;
+use std::slice::Iter;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+#[cfg(feature = "derive_serde")]
+use serde::{Deserialize, Serialize};
+
+/// This represents an element of $\mathbb{F}_q$ where
+///
+/// `q = 0x1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab`
+///
+/// is the base field of the BLS12-381 curve.
+/// The internal representation of this type is six 64-bit unsigned
+/// integers in little-endian order which account for the 381 bits required to be represented.
+/// `Fq` values are always in Montgomery form; i.e., Fq(a) = aR mod q, with R = 2^384.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "derive_serde", derive(Serialize, Deserialize))]
+pub struct Fq(pub(crate) [u64; 6]);
+
+/// Size of `Fq` element in bytes
+const SIZE: usize = 48;
+
+/// Constant representing the modulus
+/// q = 0x1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab
+const MODULUS: Fq = Fq([
+    0xb9feffffffffaaab,
+    0x1eabfffeb153ffff,
+    0x6730d2a0f6b0f624,
+    0x64774b84f38512bf,
+    0x4b1ba7b6434bacd7,
+    0x1a0111ea397fe69a,
+]);
+
+/// The modulus as u32 limbs.
+#[cfg(not(target_pointer_width = "64"))]
+const MODULUS_LIMBS_32: [u32; 12] = [
+    0xffffaaab, 0xb9feffff, 0xb153ffff, 0x1eabfffe, 0xf6b0f624, 0x6730d2a0, 0xf38512bf, 0x64774b84,
+    0x434bacd7, 0x4b1ba7b6, 0x397fe69a, 0x1a0111ea,
+];
+
+pub(crate) const MODULUS_STR: &str = "0x1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab";
+
+/// INV = -q^{-1} mod 2^64
+/// `0x89f3fffcfffcfffd`
+const INV: u64 = 0x89f3fffcfffcfffd;
+
+/// `R = 2^384 mod q`
+/// `0x15f65ec3fa80e4935c071a97a256ec6d77ce5853705257455f48985753c758baebf4000bc40c0002760900000002fffd`
+const R: Fq = Fq([
+    0x760900000002fffd,
+    0xebf4000bc40c0002,
+    0x5f48985753c758ba,
+    0x77ce585370525745,
+    0x5c071a97a256ec6d,
+    0x15f65ec3fa80e493,
+]);
+
+/// `R^2 = 2^768 mod q`
+/// `0x11988fe592cae3aa9a793e85b519952d67eb88a9939d83c08de5476c4c95b6d50a76e6a609d104f1f4df1f341c341746`
+const R2: Fq = Fq([
+    0xf4df1f341c341746,
+    0x0a76e6a609d104f1,
+    0x8de5476c4c95b6d5,
+    0x67eb88a9939d83c0,
+    0x9a793e85b519952d,
+    0x11988fe592cae3aa,
+]);
+
+/// `R^3 = 2^1152 mod q`
+/// `0xaa6346091755d4d2512d4356572472834c04e5e921e17619a53352a615e29dd315f831e03a7adf8ed48ac6bd94ca1e0`
+const R3: Fq = Fq([
+    0xed48ac6bd94ca1e0,
+    0x315f831e03a7adf8,
+    0x9a53352a615e29dd,
+    0x34c04e5e921e1761,
+    0x2512d43565724728,
+    0x0aa6346091755d4d,
+]);
+
+/// `GENERATOR = 2 mod q` is a generator of the `q - 1` order multiplicative
+/// subgroup, or in other words a primitive root of the field.
+const GENERATOR: Fq = Fq::from_raw([0x02, 0x00, 0x00, 0x00, 0x00, 0x00]);
+
+/// Size of the 2-adic sub-group of the field.
+const S: u32 = 1;
+
+/// GENERATOR^t where t * 2^s + 1 = q
+/// with t odd. In other words, this
+/// is a 2^s root of unity, namely -1.
+const ROOT_OF_UNITY: Fq = NEG_ONE;
+
+/// 1 / ROOT_OF_UNITY mod q
+const ROOT_OF_UNITY_INV: Fq = NEG_ONE;
+
+/// 1 / 2 mod q
+/// `0xd0088f51cbff34d258dd3db21a5d66bb23ba5c279c2895fb39869507b587b120f55ffff58a9ffffdcff7fffffffd556`
+pub(crate) const TWO_INV: Fq = Fq::from_raw([
+    0xdcff7fffffffd556,
+    0x0f55ffff58a9ffff,
+    0xb39869507b587b12,
+    0xb23ba5c279c2895f,
+    0x258dd3db21a5d66b,
+    0x0d0088f51cbff34d,
+]);
+
+/// GENERATOR^{2^s} where t * 2^s + 1 = q with t odd. In other words, this is a t root of unity.
+const DELTA: Fq = Fq::from_raw([0x04, 0x00, 0x00, 0x00, 0x00, 0x00]);
+
+/// `ZETA^3 = 1 mod q` where `ZETA^2 != 1 mod q`
+/// `0x5f19672fdf76ce51ba69c6076a0f77eaddb3a93be6f89688de17d813620a00022e01fffffffefffe`
+const ZETA: Fq = Fq::from_raw([
+    0x2e01fffffffefffe,
+    0xde17d813620a0002,
+    0xddb3a93be6f89688,
+    0xba69c6076a0f77ea,
+    0x5f19672fdf76ce51,
+    0x0000000000000000,
+]);
+
+/// NEG_ONE ; -1 mod q
+pub(crate) const NEG_ONE: Fq = Fq::from_raw([
+    0xb9feffffffffaaaa,
+    0x1eabfffeb153ffff,
+    0x6730d2a0f6b0f624,
+    0x64774b84f38512bf,
+    0x4b1ba7b6434bacd7,
+    0x1a0111ea397fe69a,
+]);
+
+impl_binops_additive!(Fq, Fq);
+impl_binops_multiplicative!(Fq, Fq);
+field_common_6_limbs!(
+    Fq,
+    FqRepr,
+    MODULUS,
+    INV,
+    MODULUS_STR,
+    TWO_INV,
+    ROOT_OF_UNITY_INV,
+    DELTA,
+    ZETA,
+    R,
+    R2,
+    R3
+);
+impl_sum_prod!(Fq);
+impl_from_u64_6_limbs!(Fq, R2);
+field_arithmetic_6_limbs!(Fq, MODULUS, INV, sparse);
+
+#[cfg(target_pointer_width = "64")]
+field_bits_6_limbs!(Fq, MODULUS);
+#[cfg(not(target_pointer_width = "64"))]
+field_bits_6_limbs!(Fq, MODULUS, MODULUS_LIMBS_32);
+
+extend_field_legendre!(Fq);
+
+impl Fq {
+    pub const fn size() -> usize {
+        SIZE
+    }
+}
+
+impl ff::Field for Fq {
+    const ZERO: Self = Self::zero();
+    const ONE: Self = Self::one();
+
+    fn random(mut rng: impl RngCore) -> Self {
+        Self::from_u512([
+            rng.next_u64(),
+            rng.next_u64(),
+            rng.next_u64(),
+            rng.next_u64(),
+            rng.next_u64(),
+            rng.next_u64(),
+            rng.next_u64(),
+            rng.next_u64(),
+        ])
+    }
+
+    fn double(&self) -> Self {
+        self.double()
+    }
+
+    #[inline(always)]
+    fn square(&self) -> Self {
+        self.square()
+    }
+
+    /// Computes the multiplicative inverse of this element,
+    /// failing if the element is zero.
+    fn invert(&self) -> CtOption<Self> {
+        // self^(q - 2)
+        let tmp = self.pow([
+            0xb9feffffffffaaa9,
+            0x1eabfffeb153ffff,
+            0x6730d2a0f6b0f624,
+            0x64774b84f38512bf,
+            0x4b1ba7b6434bacd7,
+            0x1a0111ea397fe69a,
+        ]);
+
+        CtOption::new(tmp, !self.ct_eq(&Self::zero()))
+    }
+
+    fn sqrt(&self) -> CtOption<Self> {
+        // q = 3 mod 4, so a square root is self^((q + 1) / 4)
+        let tmp = self.pow([
+            0xee7fbfffffffeaab,
+            0x07aaffffac54ffff,
+            0xd9cc34a83dac3d89,
+            0xd91dd2e13ce144af,
+            0x92c6e9ed90d2eb35,
+            0x0680447a8e5ff9a6,
+        ]);
+
+        CtOption::new(tmp, tmp.square().ct_eq(self))
+    }
+
+    fn sqrt_ratio(num: &Self, div: &Self) -> (Choice, Self) {
+        ff::helpers::sqrt_ratio_generic(num, div)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+/// Canonical little-endian representation of a `Fq` element.
+pub struct FqRepr {
+    pub repr: [u8; SIZE],
+}
+
+impl FqRepr {
+    /// Returns an iterator over the bytes of the canoncial representation of the element.
+    pub fn iter(&self) -> Iter<'_, u8> {
+        self.repr.iter()
+    }
+}
+
+impl Default for FqRepr {
+    fn default() -> Self {
+        FqRepr { repr: [0u8; SIZE] }
+    }
+}
+
+impl AsRef<[u8]> for FqRepr {
+    fn as_ref(&self) -> &[u8] {
+        self.repr.as_ref()
+    }
+}
+
+impl AsMut<[u8]> for FqRepr {
+    fn as_mut(&mut self) -> &mut [u8] {
+        self.repr.as_mut()
+    }
+}
+impl From<[u8; SIZE]> for FqRepr {
+    fn from(repr: [u8; SIZE]) -> Self {
+        Self { repr }
+    }
+}
+
+impl ff::PrimeField for Fq {
+    type Repr = FqRepr;
+
+    const NUM_BITS: u32 = 381;
+    const CAPACITY: u32 = 380;
+    const MODULUS: &'static str = MODULUS_STR;
+    const MULTIPLICATIVE_GENERATOR: Self = GENERATOR;
+    const ROOT_OF_UNITY: Self = ROOT_OF_UNITY;
+    const ROOT_OF_UNITY_INV: Self = ROOT_OF_UNITY_INV;
+    const TWO_INV: Self = TWO_INV;
+    const DELTA: Self = DELTA;
+    const S: u32 = S;
+
+    fn from_repr(repr: Self::Repr) -> CtOption<Self> {
+        let mut tmp = Self([0, 0, 0, 0, 0, 0]);
+        let repr = repr.repr;
+
+        tmp.0[0] = u64::from_le_bytes(repr[0..8].try_into().unwrap());
+        tmp.0[1] = u64::from_le_bytes(repr[8..16].try_into().unwrap());
+        tmp.0[2] = u64::from_le_bytes(repr[16..24].try_into().unwrap());
+        tmp.0[3] = u64::from_le_bytes(repr[24..32].try_into().unwrap());
+        tmp.0[4] = u64::from_le_bytes(repr[32..40].try_into().unwrap());
+        tmp.0[5] = u64::from_le_bytes(repr[40..48].try_into().unwrap());
+
+        // Try to subtract the modulus
+        let (_, borrow) = sbb(tmp.0[0], MODULUS.0[0], 0);
+        let (_, borrow) = sbb(tmp.0[1], MODULUS.0[1], borrow);
+        let (_, borrow) = sbb(tmp.0[2], MODULUS.0[2], borrow);
+        let (_, borrow) = sbb(tmp.0[3], MODULUS.0[3], borrow);
+        let (_, borrow) = sbb(tmp.0[4], MODULUS.0[4], borrow);
+        let (_, borrow) = sbb(tmp.0[5], MODULUS.0[5], borrow);
+
+        // If the element is smaller than MODULUS then the
+        // subtraction will underflow, producing a borrow value
+        // of 0xffff...ffff. Otherwise, it'll be zero.
+        let is_some = (borrow as u8) & 1;
+
+        // Convert to Montgomery form by computing
+        // (a.R^0 * R^2) / R = a.R
+        tmp *= &R2;
+
+        CtOption::new(tmp, Choice::from(is_some))
+    }
+
+    fn to_repr(&self) -> Self::Repr {
+        // Turn into canonical form by computing
+        // (a.R) / R = a
+        let tmp = Self::montgomery_reduce(&[
+            self.0[0], self.0[1], self.0[2], self.0[3], self.0[4], self.0[5], 0, 0, 0, 0, 0, 0,
+        ]);
+
+        let mut res = [0; SIZE];
+        res[0..8].copy_from_slice(&tmp.0[0].to_le_bytes());
+        res[8..16].copy_from_slice(&tmp.0[1].to_le_bytes());
+        res[16..24].copy_from_slice(&tmp.0[2].to_le_bytes());
+        res[24..32].copy_from_slice(&tmp.0[3].to_le_bytes());
+        res[32..40].copy_from_slice(&tmp.0[4].to_le_bytes());
+        res[40..48].copy_from_slice(&tmp.0[5].to_le_bytes());
+        res.into()
+    }
+
+    fn is_odd(&self) -> Choice {
+        Choice::from(self.to_repr().repr[0] & 1)
+    }
+}
+
+impl FromUniformBytes<64> for Fq {
+    /// Converts a 512-bit little endian integer into
+    /// an `Fq` by reducing by the modulus.
+    fn from_uniform_bytes(bytes: &[u8; 64]) -> Self {
+        Self::from_u512([
+            u64::from_le_bytes(bytes[0..8].try_into().unwrap()),
+            u64::from_le_bytes(bytes[8..16].try_into().unwrap()),
+            u64::from_le_bytes(bytes[16..24].try_into().unwrap()),
+            u64::from_le_bytes(bytes[24..32].try_into().unwrap()),
+            u64::from_le_bytes(bytes[32..40].try_into().unwrap()),
+            u64::from_le_bytes(bytes[40..48].try_into().unwrap()),
+            u64::from_le_bytes(bytes[48..56].try_into().unwrap()),
+            u64::from_le_bytes(bytes[56..64].try_into().unwrap()),
+        ])
+    }
+}
+
+impl WithSmallOrderMulGroup<3> for Fq {
+    const ZETA: Self = ZETA;
+}
+
+#[cfg(test)]
+mod test {
+    use crate::serde::SerdeObject;
+
+    use super::*;
+    use crate::ff_ext::Legendre;
+    use ark_std::{end_timer, start_timer};
+    use ff::Field;
+    use num_bigint::BigUint;
+    use rand::SeedableRng;
+    use rand_core::OsRng;
+    use rand_xorshift::XorShiftRng;
+
+    #[test]
+    fn test_sqrt() {
+        let v = (Fq::TWO_INV).square().sqrt().unwrap();
+        assert!(v == Fq::TWO_INV || (-v) == Fq::TWO_INV);
+
+        for _ in 0..10000 {
+            let a = Fq::random(OsRng);
+            let mut b = a;
+            b = b.square();
+
+            let b = b.sqrt().unwrap();
+            let mut negb = b;
+            negb = negb.neg();
+
+            assert!(a == b || a == negb);
+        }
+    }
+
+    #[test]
+    fn test_field() {
+        crate::tests::field::random_field_tests::<Fq>("bls12-381 base".to_string());
+    }
+
+    #[test]
+    fn test_delta() {
+        assert_eq!(Fq::DELTA, GENERATOR.pow([1u64 << Fq::S]));
+        assert_eq!(Fq::DELTA, Fq::MULTIPLICATIVE_GENERATOR.pow([1u64 << Fq::S]));
+    }
+
+    #[test]
+    fn test_root_of_unity() {
+        assert_eq!(Fq::ROOT_OF_UNITY, -Fq::ONE);
+        assert_eq!(Fq::ROOT_OF_UNITY * Fq::ROOT_OF_UNITY_INV, Fq::ONE);
+        // GENERATOR is a quadratic non residue
+        assert_eq!(Fq::MULTIPLICATIVE_GENERATOR.legendre(), -1);
+    }
+
+    #[test]
+    fn test_zeta() {
+        assert_eq!(Fq::ZETA * Fq::ZETA * Fq::ZETA, Fq::ONE);
+        assert_ne!(Fq::ZETA * Fq::ZETA, Fq::ONE);
+    }
+
+    #[test]
+    fn test_montgomery_constants() {
+        let modulus = BigUint::parse_bytes(&MODULUS_STR.as_bytes()[2..], 16).unwrap();
+        let to_biguint = |limbs: &[u64; 6]| {
+            limbs
+                .iter()
+                .rev()
+                .fold(BigUint::from(0u64), |acc, limb| (acc << 64) + *limb)
+        };
+
+        assert_eq!(to_biguint(&MODULUS.0), modulus);
+        let r = BigUint::from(1u64) << 384;
+        assert_eq!(to_biguint(&R.0), &r % &modulus);
+        assert_eq!(to_biguint(&R2.0), (&r * &r) % &modulus);
+        assert_eq!(to_biguint(&R3.0), (&r * &r * &r) % &modulus);
+        assert_eq!(MODULUS.0[0].wrapping_mul(INV), u64::MAX);
+
+        // `from_raw` multiplies by `R2`, so a wrong `R2` shows up here on
+        // arbitrary inputs.
+        let mut seeded_rng = XorShiftRng::seed_from_u64(0u64);
+        for _ in 0..100 {
+            let mut limbs = [0u64; 6];
+            for limb in limbs.iter_mut() {
+                *limb = seeded_rng.next_u64();
+            }
+            limbs[5] &= 0x0fff_ffff_ffff_ffff;
+
+            let expected = to_biguint(&limbs) % &modulus;
+            let mut expected = expected.to_bytes_le();
+            expected.resize(SIZE, 0);
+
+            let p = Fq::from_raw(limbs);
+            assert_eq!(p.to_repr().as_ref(), &expected[..]);
+        }
+    }
+
+    #[test]
+    fn test_from_u512() {
+        let modulus = BigUint::parse_bytes(&MODULUS_STR.as_bytes()[2..], 16).unwrap();
+
+        let mut seeded_rng = XorShiftRng::seed_from_u64(0u64);
+        for _ in 0..100 {
+            let mut bytes = [0u8; 64];
+            seeded_rng.fill_bytes(&mut bytes);
+
+            let expected = BigUint::from_bytes_le(&bytes) % &modulus;
+            let mut expected = expected.to_bytes_le();
+            expected.resize(SIZE, 0);
+
+            let p = Fq::from_uniform_bytes(&bytes);
+            assert_eq!(p.to_repr().as_ref(), &expected[..]);
+        }
+    }
+
+    #[test]
+    fn test_conversion() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        for _ in 0..100000 {
+            let a = Fq::random(&mut rng);
+            let b = Fq::from_repr(a.to_repr()).unwrap();
+            assert_eq!(a, b);
+            assert_eq!(Fq::from_bytes(&a.to_bytes()).unwrap(), a);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "bits")]
+    fn test_bits() {
+        crate::tests::field::random_bits_tests::<Fq>("Fq".to_string());
+    }
+
+    #[test]
+    fn test_serialization() {
+        crate::tests::field::random_serialization_test::<Fq>("Fq".to_string());
+        #[cfg(feature = "derive_serde")]
+        crate::tests::field::random_serde_test::<Fq>("Fq".to_string());
+    }
+
+    #[test]
+    fn test_quadratic_residue() {
+        crate::tests::field::random_quadratic_residue_test::<Fq>();
+    }
+
+    fn is_less_than(x: &[u64; 6], y: &[u64; 6]) -> bool {
+        match x[5].cmp(&y[5]) {
+            core::cmp::Ordering::Less => return true,
+            core::cmp::Ordering::Greater => return false,
+            _ => {}
+        }
+        match x[4].cmp(&y[4]) {
+            core::cmp::Ordering::Less => return true,
+            core::cmp::Ordering::Greater => return false,
+            _ => {}
+        }
+        match x[3].cmp(&y[3]) {
+            core::cmp::Ordering::Less => return true,
+            core::cmp::Ordering::Greater => return false,
+            _ => {}
+        }
+        match x[2].cmp(&y[2]) {
+            core::cmp::Ordering::Less => return true,
+            core::cmp::Ordering::Greater => return false,
+            _ => {}
+        }
+        match x[1].cmp(&y[1]) {
+            core::cmp::Ordering::Less => return true,
+            core::cmp::Ordering::Greater => return false,
+            _ => {}
+        }
+        x[0].lt(&y[0])
+    }
+
+    #[test]
+    fn test_serialization_check() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        let start = start_timer!(|| "serialize Fq");
+        // failure check
+        for _ in 0..1000000 {
+            let rand_word = [(); 6].map(|_| rng.next_u64());
+            let a = Fq(rand_word);
+            let rand_bytes = a.to_raw_bytes();
+            match is_less_than(&rand_word, &MODULUS.0) {
+                false => {
+                    assert!(Fq::from_raw_bytes(&rand_bytes).is_none());
+                }
+                _ => {
+                    assert_eq!(Fq::from_raw_bytes(&rand_bytes), Some(a));
+                }
+            }
+        }
+        end_timer!(start);
+    }
+}
@@ -0,0 +1,11 @@
+//! # `BLS12-381`
+//!
+//! Implementation of the base field tower of the BLS12-381 pairing-friendly curve.
+//!
+//! Curve parameters: https://electriccoin.co/blog/new-snark-curve/
+mod fields;
+
+pub use fields::fq::*;
+pub use fields::fq12::*;
+pub use fields::fq2::*;
+pub use fields::fq6::*;
@@ -21,7 +21,7 @@ use core::cmp;
 use core::fmt::Debug;
 use core::iter::Sum;
 use core::ops::{Add, Mul, Neg, Sub};
-use rand::RngCore;
+use rand::{CryptoRng, RngCore};
 use std::convert::TryInto;
 use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
 
@@ -53,6 +53,10 @@ new_curve_impl!(
     G2_A,
     G2_B,
     "bn256_g2",
+    // Simplified SWU through a 3-isogeny (as done for BLS12-381 G2) is not
+    // available here: the 3-division polynomial of E': y^2 = x^3 + b' is
+    // 3x(x^3 + 4b'), and -4b' is not a cube in Fq2, so the only Fq2-rational
+    // 3-isogeny has kernel x = 0 and lands on another j = 0 curve (A = 0).
     |_, _| unimplemented!(),
 );
 
@@ -112,6 +116,17 @@ const G2_GENERATOR_Y: Fq2 = Fq2 {
     ]),
 };
 
+/// The cofactor `h = 2q - r` of `G2`, the order of the BN256 twist divided by
+/// the order `r` of its prime order subgroup, as little-endian limbs.
+///
+/// It is odd, so there is no power of two to split off it.
+pub const BN256_G2_COFACTOR: [u64; 4] = [
+    0x345f2299c0f9fa8d,
+    0x06ceecda572a2489,
+    0xb85045b68181585e,
+    0x30644e72e131a029,
+];
+
 // Generated using https://github.com/ConsenSys/gnark-crypto/blob/master/ecc/utils.go
 // with `bn256::Fr::ZETA`
 // See https://github.com/demining/Endomorphism-Secp256k1/blob/main/README.md
@@ -143,23 +158,182 @@ impl group::cofactor::CofactorGroup for G1 {
     }
 }
 
+impl G1Affine {
+    /// Multiplies the point by the cofactor of `G1`.
+    ///
+    /// The BN256 `G1` curve has prime order, so its cofactor is 1 and every
+    /// point on the curve already lies in the prime order subgroup; this only
+    /// converts to projective form. Note that this differs from `G2`, whose
+    /// cofactor is not 1 and where clearing it is a real scalar
+    /// multiplication (see [`CofactorGroup::clear_cofactor`]).
+    pub fn mul_by_cofactor(&self) -> G1 {
+        G1::from(*self)
+    }
+
+    /// Returns the affine coordinates `(x, y)` of the point, or `None` if it
+    /// is the identity.
+    pub fn to_affine_coordinates(&self) -> Option<(Fq, Fq)> {
+        if bool::from(self.is_identity()) {
+            None
+        } else {
+            Some((self.x, self.y))
+        }
+    }
+
+    /// Returns the limbs of the x-coordinate.
+    ///
+    /// These are in Montgomery form, i.e. they represent `x * 2^256 mod q`.
+    pub fn x_raw_limbs(&self) -> &[u64; 4] {
+        &self.x.0
+    }
+
+    /// Returns the limbs of the y-coordinate.
+    ///
+    /// These are in Montgomery form, i.e. they represent `y * 2^256 mod q`.
+    pub fn y_raw_limbs(&self) -> &[u64; 4] {
+        &self.y.0
+    }
+
+    /// Returns the canonical little-endian limbs of the x-coordinate.
+    pub fn x_canonical_limbs(&self) -> [u64; 4] {
+        canonical_limbs(&self.x)
+    }
+
+    /// Returns the canonical little-endian limbs of the y-coordinate.
+    pub fn y_canonical_limbs(&self) -> [u64; 4] {
+        canonical_limbs(&self.y)
+    }
+
+    /// Samples a point of `G1` whose discrete logarithm with respect to the
+    /// standard generator is unknown.
+    ///
+    /// Every non-identity point of `G1` is a multiple of the generator, so
+    /// this does not avoid the generator's subgroup; instead the point is
+    /// obtained by hashing 32 random bytes to the curve, and nobody, including
+    /// the caller, learns the scalar relating it to the generator. This is the
+    /// property needed for independent commitment generators such as the
+    /// Pedersen or IPA blinding base `h`.
+    pub fn random_non_generator<R: RngCore + CryptoRng>(rng: &mut R) -> G1Affine {
+        let mut bytes = [0u8; 32];
+        rng.fill_bytes(&mut bytes);
+        G1::hash_to_curve("halo2curves:bn256:random_non_generator")(&bytes).to_affine()
+    }
+
+    /// Returns `x || y` as two canonical little-endian 32-byte encodings, for
+    /// appending the point to a transcript hash.
+    ///
+    /// Unlike [`GroupEncoding::to_bytes`] this is uncompressed and carries no
+    /// flag bits. The identity is encoded as all zeroes.
+    pub fn to_transcript_bytes(&self) -> [u8; 64] {
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(self.x.to_repr().as_ref());
+        bytes[32..].copy_from_slice(self.y.to_repr().as_ref());
+        bytes
+    }
+}
+
+fn canonical_limbs(fe: &Fq) -> [u64; 4] {
+    let repr = fe.to_repr();
+    let mut limbs = [0u64; 4];
+    for (limb, chunk) in limbs.iter_mut().zip(repr.as_ref().chunks(8)) {
+        *limb = u64::from_le_bytes(chunk.try_into().unwrap());
+    }
+    limbs
+}
+
+/// `PSI_X = (u + 9)^((q - 1) / 3)`, the x-coordinate factor of the
+/// untwist-Frobenius-twist endomorphism `ψ` on `G2`.
+pub const PSI_X: Fq2 = Fq2 {
+    c0: Fq([
+        0xb5773b104563ab30,
+        0x347f91c8a9aa6454,
+        0x7a007127242e0991,
+        0x1956bcd8118214ec,
+    ]),
+    c1: Fq([
+        0x6e849f1ea0aa4757,
+        0xaa1c7b6d89f89141,
+        0xb6e713cdfae0ca3a,
+        0x26694fbb4e82ebc3,
+    ]),
+};
+
+/// `PSI_Y = (u + 9)^((q - 1) / 2)`, the y-coordinate factor of the
+/// untwist-Frobenius-twist endomorphism `ψ` on `G2`.
+pub const PSI_Y: Fq2 = Fq2 {
+    c0: Fq([
+        0xe4bbdd0c2936b629,
+        0xbb30f162e133bacb,
+        0x31a9d1b6f9645366,
+        0x253570bea500f8dd,
+    ]),
+    c1: Fq([
+        0xa1d77ce45ffe77c7,
+        0x07affd117826d1db,
+        0x6d16bd27bb7edc6b,
+        0x2c87200285defecc,
+    ]),
+};
+
+impl G2 {
+    /// Doubles every point of `points` in place.
+    pub fn batch_double(points: &mut [G2]) {
+        for point in points.iter_mut() {
+            *point = point.double();
+        }
+    }
+
+    /// Applies the endomorphism `ψ(x, y) = (PSI_X * conj(x), PSI_Y * conj(y))`,
+    /// which acts on the prime order subgroup as multiplication by `q`.
+    pub fn psi(&self) -> G2 {
+        let mut x = self.x;
+        let mut y = self.y;
+        let mut z = self.z;
+        x.conjugate();
+        y.conjugate();
+        z.conjugate();
+        G2 {
+            x: x * PSI_X,
+            y: y * PSI_Y,
+            z,
+        }
+    }
+}
+
+impl G2Affine {
+    /// Returns the affine coordinates `(x, y)` of the point, or `None` if it
+    /// is the identity.
+    pub fn to_affine_coordinates(&self) -> Option<(Fq2, Fq2)> {
+        if bool::from(self.is_identity()) {
+            None
+        } else {
+            Some((self.x, self.y))
+        }
+    }
+
+    /// Returns the doubling of every point of `points` in projective form.
+    pub fn batch_double_return(points: &[G2Affine]) -> Vec<G2> {
+        let mut doubled: Vec<G2> = points.iter().map(G2::from).collect();
+        G2::batch_double(&mut doubled);
+        doubled
+    }
+}
+
 impl CofactorGroup for G2 {
     type Subgroup = G2;
 
     fn clear_cofactor(&self) -> Self {
-        // "0x30644e72e131a029b85045b68181585e06ceecda572a2489345f2299c0f9fa8d"
-        let e: [u8; 32] = [
-            0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81,
-            0x58, 0x5e, 0x06, 0xce, 0xec, 0xda, 0x57, 0x2a, 0x24, 0x89, 0x34, 0x5f, 0x22, 0x99,
-            0xc0, 0xf9, 0xfa, 0x8d,
-        ];
-
-        // self * COFACTOR_G2
+        // self * BN256_G2_COFACTOR
         let mut acc = G2::identity();
-        for bit in e
+        for bit in BN256_G2_COFACTOR
             .iter()
-            .flat_map(|byte| (0..8).rev().map(move |i| Choice::from((byte >> i) & 1u8)))
-            .skip(1)
+            .rev()
+            .flat_map(|limb| {
+                (0..64)
+                    .rev()
+                    .map(move |i| Choice::from(((limb >> i) & 1) as u8))
+            })
+            .skip(2)
         {
             acc = acc.double();
             acc = G2::conditional_select(&acc, &(acc + self), bit);
@@ -196,6 +370,233 @@ impl CofactorGroup for G2 {
 
 impl G1 {
     const SVDW_Z: Fq = Fq::ONE;
+
+    /// Computes `k * base` with randomized intermediate values, as a
+    /// countermeasure against side-channel attacks such as differential
+    /// power analysis.
+    ///
+    /// The scalar is split as `k = (k + r) - r` for a random `r`, so neither
+    /// of the two scalars that are actually processed depends on `k` alone.
+    /// Each of the two multiplications also starts from a fresh projective
+    /// representation `(λx : λy : λ)` of `base` with a random nonzero `λ`,
+    /// which changes every intermediate point at each bit. This costs about
+    /// twice as much as `base * k`.
+    #[cfg(feature = "countermeasures")]
+    pub fn mul_blinded(base: &G1Affine, k: &Fr, rng: &mut impl RngCore) -> G1 {
+        let mut randomized_base = || {
+            let lambda = Fq::random(&mut *rng);
+            let lambda = Fq::conditional_select(&lambda, &Fq::ONE, lambda.is_zero());
+            let p = G1 {
+                x: base.x * lambda,
+                y: base.y * lambda,
+                z: lambda,
+            };
+            G1::conditional_select(&p, &G1::identity(), base.is_identity())
+        };
+        let p0 = randomized_base();
+        let p1 = randomized_base();
+
+        let r = Fr::random(&mut *rng);
+        p0 * (k + r) - p1 * r
+    }
+
+    /// Computes `k * base` in constant time with a width-4 signed window.
+    ///
+    /// The odd multiples `[P, 3P, 5P, 7P]` are precomputed and `k` is recoded
+    /// by [`regular_wnaf4`] into 85 digits, every one of them odd and in
+    /// `[-7, 7]`. Unlike a plain NAF there are no zero digits, so every step
+    /// does the same three doublings and one addition, and the table entry
+    /// and its sign are picked with constant-time selects. An even `k` is
+    /// handled as `(k + 1) * base - base`.
+    pub fn mul_4bit_wnaf(base: &G1Affine, k: &Fr) -> G1 {
+        let p = G1::from(*base);
+        let p2 = p.double();
+        let mut table = [p; 4];
+        for i in 1..4 {
+            table[i] = table[i - 1] + p2;
+        }
+
+        let select = |digit: i8| {
+            let sign = Choice::from(((digit as u8) >> 7) & 1);
+            let mask = digit >> 7;
+            let idx = (((digit ^ mask) - mask) >> 1) as u8;
+            let mut point = G1::identity();
+            for (j, entry) in table.iter().enumerate() {
+                point = G1::conditional_select(&point, entry, (j as u8).ct_eq(&idx));
+            }
+            G1::conditional_select(&point, &-point, sign)
+        };
+
+        let mut limbs = [0u64; 4];
+        for (limb, bytes) in limbs.iter_mut().zip(k.to_repr().chunks(8)) {
+            *limb = u64::from_le_bytes(bytes.try_into().unwrap());
+        }
+        // k < r < 2^254, so adding one cannot overflow
+        let is_even = Choice::from(((limbs[0] & 1) ^ 1) as u8);
+        limbs[0] |= 1;
+
+        let digits = regular_wnaf4(limbs);
+        let mut acc = select(digits[digits.len() - 1]);
+        for digit in digits.iter().rev().skip(1) {
+            acc = acc.double().double().double();
+            acc += select(*digit);
+        }
+
+        G1::conditional_select(&acc, &(acc - p), is_even)
+    }
+
+    /// Computes `k * base` for every `k` in `scalars`.
+    ///
+    /// A table of `j * 16^i * base` for every 4-bit window `i` and digit `j`
+    /// is built once and shared by all scalars, so each product costs 64
+    /// mixed additions and no doublings. Table entries are picked with
+    /// constant-time selects.
+    ///
+    /// Building the table costs about 3.5 scalar multiplications and each
+    /// product then costs about a sixth of one, so this is faster than
+    /// multiplying each scalar separately from about 5 scalars on; see the
+    /// `bn256_fixed_base` bench.
+    pub fn fixed_base_batch_mul(base: &G1Affine, scalars: &[Fr]) -> Vec<G1> {
+        const WINDOWS: usize = 64;
+
+        let mut table = vec![G1::identity(); WINDOWS * 16];
+        let mut window_base = G1::from(*base);
+        for window in table.chunks_mut(16) {
+            for j in 1..16 {
+                window[j] = window[j - 1] + window_base;
+            }
+            window_base = window_base.double().double().double().double();
+        }
+        let mut table_affine = vec![G1Affine::identity(); table.len()];
+        G1::batch_normalize(&table, &mut table_affine);
+
+        scalars
+            .iter()
+            .map(|k| {
+                let repr = k.to_repr();
+                let mut acc = G1::identity();
+                for (i, window) in table_affine.chunks(16).enumerate() {
+                    let digit = (repr[i / 2] >> ((i % 2) * 4)) & 0xf;
+                    let mut point = G1Affine::identity();
+                    for (j, entry) in window.iter().enumerate() {
+                        point =
+                            G1Affine::conditional_select(&point, entry, (j as u8).ct_eq(&digit));
+                    }
+                    acc += point;
+                }
+                acc
+            })
+            .collect()
+    }
+
+    /// Computes `sum_i scalars[i] * bases[i]` using `scratch` as bucket
+    /// storage, so that only the converted scalars are allocated.
+    ///
+    /// This runs on the current thread. Buffers of `2^(c - 1)` points, where
+    /// `c = ceil(ln(bases.len()))`, get the same window size as
+    /// [`best_multiexp`](crate::msm::best_multiexp); smaller ones fall back
+    /// to smaller windows. See [`multiexp_serial_into`](crate::msm::multiexp_serial_into).
+    pub fn msm_into(bases: &[G1Affine], scalars: &[Fr], scratch: &mut [G1]) -> G1 {
+        crate::msm::multiexp_serial_into(scalars, bases, scratch)
+    }
+
+    /// Computes `sum_i scalars[i] * bases[i]` with the batched wNAF method.
+    ///
+    /// The odd multiples `P, 3P, ..., (2^(window - 1) - 1)P` of every base are
+    /// computed once and normalized together into a single affine table
+    /// shared by all threads. Scalars are recoded in width-`window` NAF and
+    /// scanned from the most significant digit, so that all the points of a
+    /// chunk share one chain of doublings. This pays off when the same bases
+    /// are used with many sparse scalars, e.g. the powers `[G, τG, τ²G, ...]`
+    /// of a KZG setup.
+    ///
+    /// This is variable time in the scalars.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bases` and `scalars` have different lengths or if `window`
+    /// is not in `2..=16`.
+    pub fn msm_batched_wnaf(bases: &[G1Affine], scalars: &[Fr], window: usize) -> G1 {
+        assert_eq!(bases.len(), scalars.len());
+        assert!((2..=16).contains(&window));
+
+        // odd multiples of each base, laid out contiguously
+        let table_len = 1 << (window - 2);
+        let mut table = Vec::with_capacity(bases.len() * table_len);
+        for base in bases.iter() {
+            let base = G1::from(*base);
+            let double = base.double();
+            let mut acc = base;
+            table.push(acc);
+            for _ in 1..table_len {
+                acc += double;
+                table.push(acc);
+            }
+        }
+        let mut table_affine = vec![G1Affine::identity(); table.len()];
+        G1::batch_normalize(&table, &mut table_affine);
+
+        let wnafs = scalars
+            .iter()
+            .map(|scalar| crate::msm::wnaf_form(scalar.to_repr().as_ref(), window))
+            .collect::<Vec<_>>();
+
+        let scan = |offset: usize, wnafs: &[Vec<i64>]| {
+            let num_digits = wnafs.iter().map(Vec::len).max().unwrap_or(0);
+            let mut acc = G1::identity();
+            for i in (0..num_digits).rev() {
+                acc = acc.double();
+                for (j, wnaf) in wnafs.iter().enumerate() {
+                    let digit = wnaf.get(i).copied().unwrap_or(0);
+                    let idx = (offset + j) * table_len + (digit.unsigned_abs() as usize >> 1);
+                    match digit.cmp(&0) {
+                        cmp::Ordering::Greater => acc += table_affine[idx],
+                        cmp::Ordering::Less => acc -= table_affine[idx],
+                        cmp::Ordering::Equal => {}
+                    }
+                }
+            }
+            acc
+        };
+
+        let num_threads = crate::multicore::current_num_threads();
+        if wnafs.len() > num_threads {
+            let chunk = wnafs.len() / num_threads;
+            let mut results = vec![G1::identity(); wnafs.chunks(chunk).len()];
+            crate::multicore::scope(|scope| {
+                for (i, (wnafs, acc)) in wnafs.chunks(chunk).zip(results.iter_mut()).enumerate() {
+                    let scan = &scan;
+                    scope.spawn(move |_| {
+                        *acc = scan(i * chunk, wnafs);
+                    });
+                }
+            });
+            results.iter().sum()
+        } else {
+            scan(0, &wnafs)
+        }
+    }
+}
+
+/// Recodes the odd integer `k < 2^255` into 85 digits `d_i`, all of them odd
+/// and in `[-7, 7]`, such that `k = sum_i d_i * 8^i`.
+///
+/// Each step takes `d = (k mod 16) - 8` and continues with `(k - d) / 8`,
+/// which is odd again; the last digit is what is left of `k`. There are no
+/// branches on `k`.
+fn regular_wnaf4(mut k: [u64; 4]) -> [i8; 85] {
+    let mut digits = [0i8; 85];
+    for digit in digits.iter_mut().take(84) {
+        *digit = (k[0] & 15) as i8 - 8;
+        // (k - d) / 8 = 2 * (k >> 4) + 1
+        for i in 0..3 {
+            k[i] = (k[i] >> 3) | (k[i + 1] << 61);
+        }
+        k[3] >>= 3;
+        k[0] |= 1;
+    }
+    digits[84] = k[0] as i8;
+    digits
 }
 
 #[cfg(test)]
@@ -207,6 +608,447 @@ mod tests {
     use ff::{PrimeField, WithSmallOrderMulGroup};
     use rand_core::OsRng;
 
+    #[test]
+    fn test_g1_mul_by_cofactor() {
+        use crate::group::{cofactor::CofactorGroup, prime::PrimeCurveAffine, Curve, Group};
+
+        for _ in 0..10 {
+            let p = G1::random(OsRng);
+            assert_eq!(p.to_affine().mul_by_cofactor(), p);
+            assert_eq!(p.to_affine().mul_by_cofactor(), p.clear_cofactor());
+        }
+        assert_eq!(
+            crate::bn256::G1Affine::identity().mul_by_cofactor(),
+            G1::identity()
+        );
+    }
+
+    #[test]
+    fn test_g1_limbs() {
+        use crate::bn256::{Fq, G1Affine};
+
+        let g = G1Affine::generator();
+        assert_eq!(g.x_canonical_limbs(), [1, 0, 0, 0]);
+        assert_eq!(g.y_canonical_limbs(), [2, 0, 0, 0]);
+        assert_eq!(g.x_raw_limbs(), &Fq::one().0);
+        assert_eq!(g.y_raw_limbs(), &Fq::from(2).0);
+
+        for _ in 0..10 {
+            let p = G1Affine::random(OsRng);
+            assert_eq!(Fq::from_raw(p.x_canonical_limbs()), p.x);
+            assert_eq!(Fq::from_raw(p.y_canonical_limbs()), p.y);
+            assert_eq!(Fq(*p.x_raw_limbs()), p.x);
+            assert_eq!(Fq(*p.y_raw_limbs()), p.y);
+        }
+    }
+
+    #[test]
+    fn test_to_affine_coordinates() {
+        use crate::bn256::{G1Affine, G2Affine};
+        use crate::group::{prime::PrimeCurveAffine, Curve, Group};
+        use crate::CurveAffine;
+
+        for _ in 0..10 {
+            let p = G1::random(OsRng).to_affine();
+            let coords = p.coordinates().unwrap();
+            assert_eq!(p.to_affine_coordinates(), Some((*coords.x(), *coords.y())));
+
+            let q = G2::random(OsRng).to_affine();
+            let coords = q.coordinates().unwrap();
+            assert_eq!(q.to_affine_coordinates(), Some((*coords.x(), *coords.y())));
+        }
+        assert_eq!(G1Affine::identity().to_affine_coordinates(), None);
+        assert_eq!(G2Affine::identity().to_affine_coordinates(), None);
+    }
+
+    #[test]
+    fn test_g1_encoding() {
+        use crate::bn256::{Fq, G1Affine, G1Compressed};
+        use crate::group::{prime::PrimeCurveAffine, Curve, GroupEncoding};
+        use crate::CurveAffine;
+
+        // x is stored little-endian in the first 32 bytes. Bit 7 of the last
+        // byte flags the identity and bit 6 is the parity of y.
+        let encode = |x: [u8; 32], flags: u8| {
+            let mut bytes = G1Compressed::default();
+            bytes.as_mut().copy_from_slice(&x);
+            bytes.as_mut()[31] |= flags;
+            bytes
+        };
+        let mut one = [0u8; 32];
+        one[0] = 1;
+
+        // The generator is (1, 2), with an even y
+        let g = G1Affine::generator();
+        assert_eq!(g.to_bytes(), encode(one, 0));
+        assert_eq!(G1Affine::from_bytes(&encode(one, 0)).unwrap(), g);
+
+        // -G = (1, p - 2), with an odd y
+        assert_eq!((-g).to_bytes(), encode(one, 0b0100_0000));
+        assert_eq!(G1Affine::from_bytes(&encode(one, 0b0100_0000)).unwrap(), -g);
+
+        // The identity is all zeros but for the infinity flag
+        let identity = G1Affine::identity();
+        assert_eq!(identity.to_bytes(), encode([0; 32], 0b1000_0000));
+        assert_eq!(
+            G1Affine::from_bytes(&encode([0; 32], 0b1000_0000)).unwrap(),
+            identity
+        );
+        // All zeros would be x = 0, but 3 is not a square in Fq
+        assert!(bool::from(
+            G1Affine::from_bytes(&encode([0; 32], 0)).is_none()
+        ));
+
+        // 2G, whose coordinates were computed by hand
+        // x = 0x030644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd3
+        // y = 0x15ed738c0e0a7c92e7845f96b2ae9c0a68a6a449e3538fc7ff3ebf7a5a18a2c4
+        let x = Fq::from_raw([
+            0xd3c208c16d87cfd3,
+            0xd97816a916871ca8,
+            0x9b85045b68181585,
+            0x030644e72e131a02,
+        ]);
+        let y = Fq::from_raw([
+            0xff3ebf7a5a18a2c4,
+            0x68a6a449e3538fc7,
+            0xe7845f96b2ae9c0a,
+            0x15ed738c0e0a7c92,
+        ]);
+        let two_g = G1Affine::from_xy(x, y).unwrap();
+        assert_eq!((g + g).to_affine(), two_g);
+        let x_bytes: [u8; 32] = [
+            0xd3, 0xcf, 0x87, 0x6d, 0xc1, 0x08, 0xc2, 0xd3, 0xa8, 0x1c, 0x87, 0x16, 0xa9, 0x16,
+            0x78, 0xd9, 0x85, 0x15, 0x18, 0x68, 0x5b, 0x04, 0x85, 0x9b, 0x02, 0x1a, 0x13, 0x2e,
+            0xe7, 0x44, 0x06, 0x03,
+        ];
+        assert_eq!(two_g.to_bytes(), encode(x_bytes, 0));
+        assert_eq!((-two_g).to_bytes(), encode(x_bytes, 0b0100_0000));
+        assert_eq!(G1Affine::from_bytes(&encode(x_bytes, 0)).unwrap(), two_g);
+    }
+
+    #[test]
+    fn test_g2_encoding_sign() {
+        use crate::bn256::{Fq, G2Affine};
+        use crate::group::{Curve, Group, GroupEncoding};
+
+        // The compressed encoding holds x.c0 then x.c1, little-endian. Bit 6
+        // of the last byte is sgn0(y): the parity of y.c0, and y.c1 is only
+        // looked at when y.c0 is zero.
+        let sign = |p: &G2Affine| (p.to_bytes().as_ref()[63] >> 6) & 1;
+        let parity = |e: &Fq| e.to_bytes()[0] & 1;
+
+        // The generator has an even y.c0 and an odd y.c1
+        let g = G2Affine::generator();
+        assert_eq!(parity(&g.y.c0), 0);
+        assert_eq!(parity(&g.y.c1), 1);
+        assert_eq!(sign(&g), 0);
+        assert_eq!(sign(&-g), 1);
+        assert_eq!(&g.to_bytes().as_ref()[..64], &g.x.to_bytes()[..]);
+
+        for _ in 0..100 {
+            let p = G2::random(OsRng).to_affine();
+            assert_eq!(sign(&p), parity(&p.y.c0));
+            // y.c0 is nonzero, so negation flips the sign
+            assert_eq!(sign(&-p), 1 - sign(&p));
+            assert_eq!(G2Affine::from_bytes(&p.to_bytes()).unwrap(), p);
+            assert_eq!(G2Affine::from_bytes(&(-p).to_bytes()).unwrap(), -p);
+        }
+    }
+
+    #[test]
+    fn test_g2_encoding_sign_zero_c0() {
+        use super::G2_B;
+        use crate::bn256::{Fq, Fq2, G2Affine, BN256_P};
+        use crate::group::GroupEncoding;
+        use crate::CurveAffine;
+        use num_bigint::BigUint;
+
+        // No point of the prime order subgroup with y.c0 = 0 is known, so
+        // build one on the twist curve instead, which is enough to exercise
+        // the encoding: y = c1 * u needs x^3 = -c1^2 - b.
+        let q = BN256_P
+            .iter()
+            .rev()
+            .fold(BigUint::from(0u64), |acc, limb| (acc << 64) + *limb);
+        let order = &q * &q - 1u64;
+        // The order is 9 * m with m coprime to 3, so t^k with 3k = 1 mod m
+        // is a cube root of a cube t up to a 9-th root of unity.
+        let m = &order / 9u64;
+        let k = (BigUint::from(2u64) * &m + 1u64) / 3u64;
+        assert_eq!((BigUint::from(3u64) * &k) % &m, BigUint::from(1u64));
+        let omega = (2..)
+            .map(|i| Fq2::new(Fq::from(i), Fq::one()).pow_vartime(m.to_u64_digits()))
+            .find(|w| w.pow_vartime([3]) != Fq2::one())
+            .unwrap();
+
+        let p = (1..)
+            .find_map(|i| {
+                let y = Fq2::new(Fq::zero(), Fq::from(i));
+                let t = y.square() - G2_B;
+                let x0 = t.pow_vartime(k.to_u64_digits());
+                (0..9)
+                    .map(|j| x0 * omega.pow_vartime([j]))
+                    .find(|x| x.square() * x == t)
+                    .map(|x| G2Affine::from_xy(x, y).unwrap())
+            })
+            .unwrap();
+        assert!(bool::from(p.y.c0.is_zero()));
+
+        let sign = |p: &G2Affine| (p.to_bytes().as_ref()[63] >> 6) & 1;
+        assert_eq!(sign(&p), p.y.c1.to_bytes()[0] & 1);
+        assert_eq!(sign(&-p), 1 - sign(&p));
+        assert_eq!(G2Affine::from_bytes(&p.to_bytes()).unwrap(), p);
+        assert_eq!(G2Affine::from_bytes(&(-p).to_bytes()).unwrap(), -p);
+    }
+
+    #[test]
+    fn test_g2_generator_encoding() {
+        use crate::bn256::{Fq, G2Affine};
+        use crate::group::GroupEncoding;
+
+        let g = G2Affine::generator();
+        let bytes = g.to_bytes();
+        assert_eq!(G2Affine::from_bytes(&bytes).unwrap(), g);
+
+        // Parse the encoding by hand: x.c0 in bytes 0..32 and x.c1 in bytes
+        // 32..64, both little-endian, with the flags in the top two bits of
+        // the last byte
+        let bytes = bytes.as_ref();
+        assert_eq!(bytes.len(), 64);
+        let flags = bytes[63] >> 6;
+        let mut c1_bytes = [0u8; 32];
+        c1_bytes.copy_from_slice(&bytes[32..]);
+        c1_bytes[31] &= 0b0011_1111;
+        let limbs = |bytes: &[u8]| -> [u64; 4] {
+            let mut limbs = [0u64; 4];
+            for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks(8)) {
+                *limb = u64::from_le_bytes(chunk.try_into().unwrap());
+            }
+            limbs
+        };
+
+        // The coordinates of the generator, as given in EIP-197
+        // x.c0 = 0x1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed
+        // x.c1 = 0x198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2
+        let x_c0 = [
+            0x46debd5cd992f6ed,
+            0x674322d4f75edadd,
+            0x426a00665e5c4479,
+            0x1800deef121f1e76,
+        ];
+        let x_c1 = [
+            0x97e485b7aef312c2,
+            0xf1aa493335a9e712,
+            0x7260bfb731fb5d25,
+            0x198e9393920d483a,
+        ];
+        assert_eq!(limbs(&bytes[..32]), x_c0);
+        assert_eq!(limbs(&c1_bytes), x_c1);
+        assert_eq!(Fq::from_raw(x_c0), g.x.c0);
+        assert_eq!(Fq::from_raw(x_c1), g.x.c1);
+
+        // Not the identity, and y.c0 is even
+        assert_eq!(flags, 0);
+        assert_eq!(g.y.c0.to_bytes()[0] & 1, 0);
+    }
+
+    #[cfg(feature = "countermeasures")]
+    #[test]
+    fn test_mul_blinded() {
+        use crate::bn256::G1Affine;
+        use crate::group::{prime::PrimeCurveAffine, Curve, Group};
+
+        let g = G1Affine::generator();
+        for _ in 0..20 {
+            let base = G1::random(OsRng).to_affine();
+            let k = Fr::random(OsRng);
+            assert_eq!(G1::mul_blinded(&base, &k, &mut OsRng), base * k);
+            assert_eq!(G1::mul_blinded(&g, &k, &mut OsRng), g * k);
+        }
+        assert_eq!(G1::mul_blinded(&g, &Fr::zero(), &mut OsRng), G1::identity());
+        assert_eq!(G1::mul_blinded(&g, &Fr::one(), &mut OsRng), G1::from(g));
+        assert_eq!(
+            G1::mul_blinded(&G1Affine::identity(), &Fr::random(OsRng), &mut OsRng),
+            G1::identity()
+        );
+    }
+
+    #[test]
+    fn test_g2_cofactor() {
+        use super::BN256_G2_COFACTOR;
+        use crate::arithmetic::{adc, sbb};
+        use crate::bn256::{Fq2, G2Affine, BN256_P, BN256_R};
+        use crate::group::{cofactor::CofactorGroup, Group};
+
+        // h = 2q - r
+        let mut two_q = [0u64; 5];
+        let mut carry = 0;
+        for i in 0..4 {
+            (two_q[i], carry) = adc(BN256_P[i], BN256_P[i], carry);
+        }
+        two_q[4] = carry;
+        let mut h = [0u64; 4];
+        let mut borrow = 0;
+        for i in 0..4 {
+            (h[i], borrow) = sbb(two_q[i], BN256_R[i], borrow);
+        }
+        let (top, _) = sbb(two_q[4], 0, borrow);
+        assert_eq!(top, 0);
+        assert_eq!(h, BN256_G2_COFACTOR);
+        assert_eq!(BN256_G2_COFACTOR[0] & 1, 1);
+
+        // A point of the twist that is outside of G2
+        let p = loop {
+            let x = Fq2::random(OsRng);
+            if let Some(y) = Option::<Fq2>::from(G2Affine::y2(x).sqrt()) {
+                break G2::from(G2Affine { x, y });
+            }
+        };
+        assert!(!bool::from(p.is_torsion_free()));
+        assert!(bool::from(p.clear_cofactor().is_torsion_free()));
+
+        // On G2 itself, clearing the cofactor is multiplying by h mod r
+        let q = G2::random(OsRng);
+        assert_eq!(q.clear_cofactor(), q * Fr::from_raw(BN256_G2_COFACTOR));
+    }
+
+    #[test]
+    fn test_mul_4bit_wnaf() {
+        use super::regular_wnaf4;
+        use crate::bn256::G1Affine;
+        use crate::group::{prime::PrimeCurveAffine, Curve, Group};
+        use rand_core::RngCore;
+
+        for _ in 0..100 {
+            let mut limbs = [
+                OsRng.next_u64(),
+                OsRng.next_u64(),
+                OsRng.next_u64(),
+                OsRng.next_u64() >> 2,
+            ];
+            limbs[0] |= 1;
+            let digits = regular_wnaf4(limbs);
+            assert!(digits.iter().all(|d| d & 1 == 1 && (-7..=7).contains(d)));
+            let k = digits.iter().rev().fold(Fr::zero(), |acc, d| {
+                let abs = Fr::from(d.unsigned_abs() as u64);
+                acc * Fr::from(8) + if *d < 0 { -abs } else { abs }
+            });
+            assert_eq!(k, Fr::from_raw(limbs));
+        }
+
+        let g = G1Affine::generator();
+        for k in [Fr::zero(), Fr::one(), Fr::from(2), -Fr::one(), -Fr::from(2)] {
+            assert_eq!(G1::mul_4bit_wnaf(&g, &k), g * k);
+        }
+        for _ in 0..50 {
+            let base = G1::random(OsRng).to_affine();
+            let k = Fr::random(OsRng);
+            assert_eq!(G1::mul_4bit_wnaf(&base, &k), base * k);
+        }
+        assert_eq!(
+            G1::mul_4bit_wnaf(&G1Affine::identity(), &Fr::random(OsRng)),
+            G1::identity()
+        );
+    }
+
+    #[test]
+    fn test_msm_into() {
+        use crate::bn256::G1Affine;
+        use crate::group::{Curve, Group};
+        use crate::msm::best_multiexp;
+
+        let bases: Vec<G1Affine> = (0..200).map(|_| G1::random(OsRng).to_affine()).collect();
+        let scalars: Vec<Fr> = (0..200).map(|_| Fr::random(OsRng)).collect();
+        let mut scratch = vec![G1::identity(); 1 << 5];
+        for n in [0, 1, 3, 10, 31, 32, 200] {
+            let expected = best_multiexp(&scalars[..n], &bases[..n]);
+            assert_eq!(
+                G1::msm_into(&bases[..n], &scalars[..n], &mut scratch),
+                expected
+            );
+            // Smaller buffers than needed for the default window
+            for len in [1, 2, 7] {
+                assert_eq!(
+                    G1::msm_into(&bases[..n], &scalars[..n], &mut scratch[..len]),
+                    expected
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_msm_batched_wnaf() {
+        use crate::bn256::G1Affine;
+        use crate::group::{Curve, Group};
+        use crate::msm::best_multiexp;
+
+        let bases: Vec<G1Affine> = (0..100).map(|_| G1::random(OsRng).to_affine()).collect();
+        let scalars: Vec<Fr> = (0..100).map(|_| Fr::random(OsRng)).collect();
+        let expected = best_multiexp(&scalars, &bases);
+        for window in 2..8 {
+            assert_eq!(G1::msm_batched_wnaf(&bases, &scalars, window), expected);
+        }
+
+        // Powers of tau, as in a KZG setup
+        let tau = Fr::random(OsRng);
+        let g = G1::generator();
+        let powers: Vec<G1Affine> = (0..16u64).map(|i| (g * tau.pow([i])).to_affine()).collect();
+        let scalars = &scalars[..16];
+        assert_eq!(
+            G1::msm_batched_wnaf(&powers, scalars, 4),
+            best_multiexp(scalars, &powers)
+        );
+
+        assert_eq!(G1::msm_batched_wnaf(&[], &[], 4), G1::identity());
+        assert_eq!(
+            G1::msm_batched_wnaf(&bases[..1], &[Fr::zero()], 4),
+            G1::identity()
+        );
+    }
+
+    #[test]
+    fn test_g2_batch_double() {
+        use crate::bn256::G2Affine;
+        use crate::group::{Curve, Group};
+
+        let mut points: Vec<G2> = (0..10).map(|_| G2::random(OsRng)).collect();
+        points.push(G2::identity());
+        let affine: Vec<G2Affine> = points.iter().map(|p| p.to_affine()).collect();
+        let expected: Vec<G2> = points.iter().map(|p| p + p).collect();
+
+        assert_eq!(G2Affine::batch_double_return(&affine), expected);
+        G2::batch_double(&mut points);
+        assert_eq!(points, expected);
+
+        G2::batch_double(&mut []);
+        assert!(G2Affine::batch_double_return(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_psi() {
+        use crate::bn256::{G2Affine, PSI_X, PSI_Y};
+        use crate::group::{Curve, Group};
+
+        // q mod r
+        let q = Fr::from_raw([0xf83e9682e87cfd46, 0x6f4d8248eeb859fb, 0, 0]);
+        for _ in 0..10 {
+            let p = G2::random(OsRng);
+            assert_eq!(p.psi(), p * q);
+
+            let p = p.to_affine();
+            let mut x = p.x;
+            let mut y = p.y;
+            x.conjugate();
+            y.conjugate();
+            let expected = G2Affine {
+                x: x * PSI_X,
+                y: y * PSI_Y,
+            };
+            assert_eq!(G2::from(p).psi().to_affine(), expected);
+        }
+        assert_eq!(G2::identity().psi(), G2::identity());
+    }
+
     #[test]
     fn test_hash_to_curve() {
         crate::tests::curve::hash_to_curve_test::<G1>();
@@ -301,6 +1143,67 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_fixed_base_batch_mul() {
+        use crate::bn256::G1Affine;
+        use crate::group::{prime::PrimeCurveAffine, Curve, Group};
+
+        let base = G1::random(OsRng).to_affine();
+        let mut scalars = (0..20).map(|_| Fr::random(OsRng)).collect::<Vec<_>>();
+        scalars.extend([Fr::zero(), Fr::one(), -Fr::one()]);
+
+        let products = G1::fixed_base_batch_mul(&base, &scalars);
+        assert_eq!(products.len(), scalars.len());
+        for (p, k) in products.iter().zip(scalars.iter()) {
+            assert_eq!(*p, base * k);
+        }
+
+        let products = G1::fixed_base_batch_mul(&G1Affine::identity(), &scalars);
+        assert!(products.iter().all(|p| bool::from(p.is_identity())));
+        assert!(G1::fixed_base_batch_mul(&base, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_random_non_generator() {
+        use crate::bn256::G1Affine;
+        use crate::group::prime::PrimeCurveAffine;
+        use crate::CurveAffine;
+
+        let a = G1Affine::random_non_generator(&mut OsRng);
+        let b = G1Affine::random_non_generator(&mut OsRng);
+        for p in [a, b] {
+            assert!(bool::from(p.is_on_curve()));
+            assert!(!bool::from(p.is_identity()));
+            assert_ne!(p, G1Affine::generator());
+        }
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_transcript_bytes() {
+        use crate::bn256::G1Affine;
+        use crate::group::{prime::PrimeCurveAffine, Curve, Group};
+
+        for _ in 0..100 {
+            let p = G1::random(OsRng).to_affine();
+            let bytes = p.to_transcript_bytes();
+            assert_eq!(bytes[..32], p.x.to_repr()[..]);
+            assert_eq!(bytes[32..], p.y.to_repr()[..]);
+        }
+
+        let g = G1Affine::generator().to_transcript_bytes();
+        assert_eq!(g[0], 1);
+        assert_eq!(g[32], 2);
+        assert!(g[1..32].iter().chain(&g[33..]).all(|b| *b == 0));
+        assert_eq!(G1Affine::identity().to_transcript_bytes(), [0u8; 64]);
+    }
+
+    #[test]
+    fn test_compressed_roundtrip() {
+        crate::tests::curve::compressed_roundtrip_test::<G1>(1000);
+        crate::tests::curve::compressed_roundtrip_test::<G2>(1000);
+    }
+
     #[test]
     fn test_serialization() {
         crate::tests::curve::random_serialization_test::<G1>();
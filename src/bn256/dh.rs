@@ -0,0 +1,54 @@
+//! Diffie-Hellman key agreement over the BN256 `G1` group.
+
+use super::{Fr, G1Affine, G1};
+use crate::ff::PrimeField;
+use crate::group::{prime::PrimeCurveAffine, Curve};
+use sha2::{Digest, Sha256};
+use subtle::CtOption;
+
+/// Returns the public key `sk * G` for the secret key `sk`, where `G` is the
+/// standard `G1` generator.
+pub fn dh_public_key(sk: &Fr) -> G1Affine {
+    (G1Affine::generator() * sk).to_affine()
+}
+
+/// Computes the shared secret between the secret key `sk` and the peer's
+/// public key `pk`: the SHA-256 digest of the canonical little-endian
+/// encoding of the x-coordinate of `sk * pk`.
+///
+/// Returns `None` if `sk * pk` is the identity, which as `G1` has prime order
+/// happens exactly when `pk` is the identity or `sk` is zero. The digest is
+/// always computed, so this does not leak which case occurred.
+pub fn dh_exchange(sk: &Fr, pk: &G1Affine) -> CtOption<[u8; 32]> {
+    let shared = (G1::from(*pk) * sk).to_affine();
+    let secret = Sha256::digest(shared.x.to_repr()).into();
+    CtOption::new(secret, !shared.is_identity())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ff::Field;
+    use rand_core::OsRng;
+
+    #[test]
+    fn test_dh_exchange() {
+        for _ in 0..10 {
+            let sk_a = Fr::random(OsRng);
+            let sk_b = Fr::random(OsRng);
+            let pk_a = dh_public_key(&sk_a);
+            let pk_b = dh_public_key(&sk_b);
+
+            let shared = dh_exchange(&sk_a, &pk_b).unwrap();
+            assert_eq!(shared, dh_exchange(&sk_b, &pk_a).unwrap());
+            assert_ne!(shared, dh_exchange(&sk_a, &pk_a).unwrap());
+
+            assert!(bool::from(
+                dh_exchange(&sk_a, &G1Affine::identity()).is_none()
+            ));
+            assert!(bool::from(dh_exchange(&Fr::zero(), &pk_b).is_none()));
+        }
+
+        assert_eq!(dh_public_key(&Fr::one()), G1Affine::generator());
+    }
+}
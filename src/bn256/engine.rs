@@ -3,7 +3,7 @@ use crate::bn256::curve::*;
 use crate::bn256::fq::*;
 use crate::bn256::fq12::*;
 use crate::bn256::fq2::*;
-use crate::bn256::fq6::FROBENIUS_COEFF_FQ6_C1;
+use crate::bn256::fq6::{Fq6, FROBENIUS_COEFF_FQ6_C1};
 use crate::bn256::fr::*;
 use crate::ff::{Field, PrimeField};
 use crate::group::cofactor::CofactorCurveAffine;
@@ -13,7 +13,7 @@ use core::iter::Sum;
 use core::ops::{Add, Mul, MulAssign, Neg, Sub};
 use pairing::{Engine, MillerLoopResult, MultiMillerLoop, PairingCurveAffine};
 use rand_core::RngCore;
-use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
 
 pub const BN_X: u64 = 4965661367192848881;
 
@@ -96,6 +96,88 @@ impl Gt {
     pub fn double(&self) -> Gt {
         Gt(self.0.square())
     }
+
+    /// Compresses this element to the 192 bytes of a single `Fq6` element.
+    ///
+    /// This is T2 torus compression, not a square root based scheme. `Gt`
+    /// lies in the norm-one torus `T2(Fq6)`, whose elements `g0 + g1 * w`
+    /// satisfy `g0^2 - g1^2 * v = 1`. Any such element with `g1 != 0` is
+    /// determined by `c = (1 + g0) / g1`, since it equals `(c + w) / (c - w)`,
+    /// and the identity is encoded as `c = 0`. The six `Fq` coefficients of
+    /// `c` are written in little-endian order, `c0.c0` first.
+    pub fn compress(&self) -> [u8; 192] {
+        let g1_inv = self.0.c1.invert().unwrap_or(Fq6::ZERO);
+        let c = (Fq6::ONE + self.0.c0) * g1_inv;
+
+        let mut res = [0u8; 192];
+        for (chunk, coeff) in res.chunks_mut(32).zip(
+            [c.c0, c.c1, c.c2]
+                .iter()
+                .flat_map(|c| [c.c0, c.c1].into_iter()),
+        ) {
+            chunk.copy_from_slice(&coeff.to_bytes());
+        }
+        res
+    }
+
+    /// Decompresses an element encoded with [`Gt::compress`], checking that
+    /// it lies in the prime order subgroup.
+    ///
+    /// The subgroup check raises the decoded element to the power `r`, which
+    /// costs about as much as a final exponentiation and dominates the
+    /// decoding itself. Only this function is guaranteed to return a valid
+    /// `Gt` element; use [`Gt::decompress_unchecked`] for trusted inputs.
+    pub fn decompress(bytes: &[u8; 192]) -> CtOption<Gt> {
+        Gt::decompress_unchecked(bytes).and_then(|gt| {
+            // f^r = 1
+            let is_torsion_free = gt.0.pow_vartime(BN256_R).ct_eq(&Fq12::ONE);
+            CtOption::new(gt, is_torsion_free)
+        })
+    }
+
+    /// Decompresses an element encoded with [`Gt::compress`] without checking
+    /// subgroup membership.
+    ///
+    /// This computes `(c + w) / (c - w) = ((c^2 + v) + 2c * w) / (c^2 - v)`,
+    /// where `c^2 - v` is always invertible since `v` is not a square in
+    /// `Fq6`, so it costs a single `Fq6` inversion. Fails only if a
+    /// coefficient is not canonically encoded.
+    ///
+    /// The result is only guaranteed to have norm one, i.e. to lie in the
+    /// norm-one torus `T2(Fq6)` of order `p^6 + 1`. For untrusted input it
+    /// may lie outside the cyclotomic subgroup of order `p^4 - p^2 + 1`, so
+    /// `cyclotomic_square` and anything else assuming membership in it are
+    /// not valid on the result, let alone anything assuming the prime order
+    /// subgroup. Use [`Gt::decompress`] to get a valid `Gt` element.
+    pub fn decompress_unchecked(bytes: &[u8; 192]) -> CtOption<Gt> {
+        let mut coeffs = [Fq::zero(); 6];
+        let mut is_some = Choice::from(1);
+        for (coeff, chunk) in coeffs.iter_mut().zip(bytes.chunks(32)) {
+            let fq = Fq::from_bytes(chunk.try_into().unwrap());
+            is_some &= fq.is_some();
+            *coeff = fq.unwrap_or(Fq::zero());
+        }
+        let c = Fq6 {
+            c0: Fq2::new(coeffs[0], coeffs[1]),
+            c1: Fq2::new(coeffs[2], coeffs[3]),
+            c2: Fq2::new(coeffs[4], coeffs[5]),
+        };
+        let v = Fq6 {
+            c0: Fq2::ZERO,
+            c1: Fq2::ONE,
+            c2: Fq2::ZERO,
+        };
+
+        let c2 = c.square();
+        let den_inv = (c2 - v).invert().unwrap();
+        let f = Fq12 {
+            c0: (c2 + v) * den_inv,
+            c1: c.double() * den_inv,
+        };
+        let f = Fq12::conditional_select(&f, &Fq12::ONE, c.ct_eq(&Fq6::ZERO));
+
+        CtOption::new(Gt(f), is_some)
+    }
 }
 
 impl<'a> Neg for &'a Gt {
@@ -470,92 +552,80 @@ impl MillerLoopResult for Gt {
             *f = res;
         }
 
-        let r = self.0;
-        let mut f1 = self.0;
-        f1.conjugate();
+        let r = self.0.final_exp_easy();
 
-        Gt(r.invert()
-            .map(|mut f2| {
-                let mut r = f1;
-                r.mul_assign(&f2);
-                f2 = r;
-                r.frobenius_map(2);
-                r.mul_assign(&f2);
+        let mut fp = r;
+        fp.frobenius_map(1);
 
-                let mut fp = r;
-                fp.frobenius_map(1);
+        let mut fp2 = r;
+        fp2.frobenius_map(2);
+        let mut fp3 = fp2;
+        fp3.frobenius_map(1);
 
-                let mut fp2 = r;
-                fp2.frobenius_map(2);
-                let mut fp3 = fp2;
-                fp3.frobenius_map(1);
+        let mut fu = r;
+        exp_by_x(&mut fu);
 
-                let mut fu = r;
-                exp_by_x(&mut fu);
+        let mut fu2 = fu;
+        exp_by_x(&mut fu2);
 
-                let mut fu2 = fu;
-                exp_by_x(&mut fu2);
+        let mut fu3 = fu2;
+        exp_by_x(&mut fu3);
 
-                let mut fu3 = fu2;
-                exp_by_x(&mut fu3);
+        let mut y3 = fu;
+        y3.frobenius_map(1);
 
-                let mut y3 = fu;
-                y3.frobenius_map(1);
+        let mut fu2p = fu2;
+        fu2p.frobenius_map(1);
 
-                let mut fu2p = fu2;
-                fu2p.frobenius_map(1);
+        let mut fu3p = fu3;
+        fu3p.frobenius_map(1);
 
-                let mut fu3p = fu3;
-                fu3p.frobenius_map(1);
+        let mut y2 = fu2;
+        y2.frobenius_map(2);
 
-                let mut y2 = fu2;
-                y2.frobenius_map(2);
+        let mut y0 = fp;
+        y0.mul_assign(&fp2);
+        y0.mul_assign(&fp3);
 
-                let mut y0 = fp;
-                y0.mul_assign(&fp2);
-                y0.mul_assign(&fp3);
+        let mut y1 = r;
+        y1.conjugate();
 
-                let mut y1 = r;
-                y1.conjugate();
+        let mut y5 = fu2;
+        y5.conjugate();
 
-                let mut y5 = fu2;
-                y5.conjugate();
+        y3.conjugate();
 
-                y3.conjugate();
+        let mut y4 = fu;
+        y4.mul_assign(&fu2p);
+        y4.conjugate();
 
-                let mut y4 = fu;
-                y4.mul_assign(&fu2p);
-                y4.conjugate();
+        let mut y6 = fu3;
+        y6.mul_assign(&fu3p);
+        y6.conjugate();
 
-                let mut y6 = fu3;
-                y6.mul_assign(&fu3p);
-                y6.conjugate();
+        y6.cyclotomic_square();
+        y6.mul_assign(&y4);
+        y6.mul_assign(&y5);
 
-                y6.cyclotomic_square();
-                y6.mul_assign(&y4);
-                y6.mul_assign(&y5);
+        let mut t1 = y3;
+        t1.mul_assign(&y5);
+        t1.mul_assign(&y6);
 
-                let mut t1 = y3;
-                t1.mul_assign(&y5);
-                t1.mul_assign(&y6);
+        y6.mul_assign(&y2);
 
-                y6.mul_assign(&y2);
+        t1.cyclotomic_square();
+        t1.mul_assign(&y6);
+        t1.cyclotomic_square();
 
-                t1.cyclotomic_square();
-                t1.mul_assign(&y6);
-                t1.cyclotomic_square();
+        let mut t0 = t1;
+        t0.mul_assign(&y1);
 
-                let mut t0 = t1;
-                t0.mul_assign(&y1);
+        t1.mul_assign(&y0);
 
-                t1.mul_assign(&y0);
+        t0.cyclotomic_square();
+        t0.mul_assign(&t1);
 
-                t0.cyclotomic_square();
-                t0.mul_assign(&t1);
-
-                t0
-            })
-            .unwrap())
+        Gt(t0)
     }
 }
 
@@ -629,6 +699,27 @@ pub fn pairing(g1: &G1Affine, g2: &G2Affine) -> Gt {
     u.final_exponentiation()
 }
 
+/// Computes `e(P_0, Q_0) * e(P_1, Q_1) * ...`, running the Miller loop of each
+/// pair on its own thread.
+///
+/// The Miller loop outputs are multiplied together in `Fq12` and a single
+/// final exponentiation is applied to the product, so the result is the same
+/// as with [`multi_miller_loop`]; only the shared squarings of the sequential
+/// loop are traded for parallelism across pairs.
+#[cfg(feature = "multicore")]
+pub fn multi_pairing_parallel(pairs: &[(&G1Affine, &G2Affine)]) -> Gt {
+    use crate::multicore::{IntoParallelRefIterator, ParallelIterator};
+
+    pairs
+        .par_iter()
+        .map(|(p, q)| {
+            let q = G2Prepared::from_affine(**q);
+            multi_miller_loop(&[(p, &q)])
+        })
+        .reduce(Gt::identity, |a, b| a + b)
+        .final_exponentiation()
+}
+
 #[derive(Clone, Debug)]
 pub struct Bn256;
 
@@ -836,3 +927,131 @@ fn random_miller_loop_tests() {
         assert_eq!(abcd, abcd_with_double_loop);
     }
 }
+
+#[test]
+fn test_gt_compression() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let identity = Gt::identity();
+    assert_eq!(identity.compress(), [0u8; 192]);
+    assert_eq!(Gt::decompress(&identity.compress()).unwrap(), identity);
+
+    let base = pairing(&G1Affine::generator(), &G2Affine::generator());
+    for _ in 0..10 {
+        let gt = base * Fr::random(&mut rng);
+        let compressed = gt.compress();
+        assert_eq!(Gt::decompress(&compressed).unwrap(), gt);
+        assert_eq!(Gt::decompress(&(-gt).compress()).unwrap(), -gt);
+        assert_eq!(Gt::decompress_unchecked(&compressed).unwrap(), gt);
+    }
+
+    // Non canonical coefficients
+    assert!(bool::from(Gt::decompress(&[0xff; 192]).is_none()));
+    assert!(bool::from(Gt::decompress_unchecked(&[0xff; 192]).is_none()));
+
+    // An element of the norm-one torus T2(Fq6) outside of the prime order
+    // subgroup
+    let mut bytes = [0u8; 192];
+    bytes[0] = 1;
+    assert!(bool::from(Gt::decompress(&bytes).is_none()));
+    let gt = Gt::decompress_unchecked(&bytes).unwrap();
+    assert_eq!(gt.compress(), bytes);
+}
+
+#[cfg(feature = "multicore")]
+#[test]
+fn test_multi_pairing_parallel() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let g1s: Vec<G1Affine> = (0..8)
+        .map(|_| G1Affine::from(G1::random(&mut rng)))
+        .collect();
+    let mut g2s: Vec<G2Affine> = (0..8)
+        .map(|_| G2Affine::from(G2::random(&mut rng)))
+        .collect();
+    g2s[3] = G2Affine::identity();
+    let pairs: Vec<_> = g1s.iter().zip(g2s.iter()).collect();
+
+    let expected = pairs
+        .iter()
+        .fold(Gt::identity(), |acc, (p, q)| acc + pairing(p, q));
+    assert_eq!(multi_pairing_parallel(&pairs), expected);
+
+    let prepared: Vec<G2Prepared> = g2s.iter().map(|q| G2Prepared::from(*q)).collect();
+    let terms: Vec<_> = g1s.iter().zip(prepared.iter()).collect();
+    assert_eq!(
+        multi_pairing_parallel(&pairs),
+        multi_miller_loop(&terms).final_exponentiation()
+    );
+
+    assert_eq!(multi_pairing_parallel(&[]), Gt::identity());
+}
+
+#[test]
+fn test_gt_group_law() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let base = pairing(&G1Affine::generator(), &G2Affine::generator());
+    assert!(base != Gt::identity());
+    // The pairing target has order r
+    assert_eq!(base.0.pow_vartime(BN256_R), Fq12::ONE);
+    assert_eq!(base * -Fr::ONE, -base);
+
+    for _ in 0..10 {
+        let a = (base * Fr::random(&mut rng)).0;
+        let b = (base * Fr::random(&mut rng)).0;
+        let c = (base * Fr::random(&mut rng)).0;
+
+        assert_eq!(a * b, b * a);
+        assert_eq!((a * b) * c, a * (b * c));
+        assert_eq!(a * Fq12::ONE, a);
+        assert_eq!(a * a.invert().unwrap(), Fq12::ONE);
+        assert_eq!(a.pow_vartime(BN256_R), Fq12::ONE);
+    }
+}
+
+#[test]
+fn test_pairing_bilinearity() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    let g1 = G1Affine::generator();
+    let g2 = G2Affine::generator();
+    let base = pairing(&g1, &g2);
+    assert!(base != Gt::identity());
+
+    for _ in 0..10 {
+        let a = Fr::random(&mut rng);
+        let b = Fr::random(&mut rng);
+        let a_g1 = G1Affine::from(g1 * a);
+        let b_g2 = G2Affine::from(g2 * b);
+
+        // e([a]G1, [b]G2) = e(G1, G2)^(ab)
+        assert_eq!(pairing(&a_g1, &b_g2), base * (a * b));
+
+        // Linearity in each argument
+        assert_eq!(pairing(&a_g1, &g2), base * a);
+        assert_eq!(pairing(&g1, &b_g2), base * b);
+        let p = G1::random(&mut rng);
+        let q = G2::random(&mut rng);
+        assert_eq!(
+            pairing(&(p + a_g1).into(), &b_g2),
+            pairing(&p.into(), &b_g2) + pairing(&a_g1, &b_g2)
+        );
+        assert_eq!(
+            pairing(&a_g1, &(q + b_g2).into()),
+            pairing(&a_g1, &q.into()) + pairing(&a_g1, &b_g2)
+        );
+    }
+}
@@ -25,20 +25,37 @@ use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
 // The internal representation of this type is four 64-bit unsigned
 // integers in little-endian order. `Fq` values are always in
 // Montgomery form; i.e., Fq(a) = aR mod q, with R = 2^256.
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Eq)]
 pub struct Fq(pub(crate) [u64; 4]);
 
+/// Equality is checked in constant time, through [`ConstantTimeEq`].
+impl PartialEq for Fq {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl core::hash::Hash for Fq {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
 #[cfg(feature = "derive_serde")]
 crate::serialize_deserialize_32_byte_primefield!(Fq);
 
-/// Constant representing the modulus
+/// The base field characteristic in little-endian 64-bit limbs
 /// q = 0x30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47
-const MODULUS: Fq = Fq([
+pub const BN256_P: [u64; 4] = [
     0x3c208c16d87cfd47,
     0x97816a916871ca8d,
     0xb85045b68181585d,
     0x30644e72e131a029,
-]);
+];
+
+/// Constant representing the modulus
+/// q = 0x30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47
+const MODULUS: Fq = Fq(BN256_P);
 
 /// The modulus as u32 limbs.
 #[cfg(not(target_pointer_width = "64"))]
@@ -56,6 +73,10 @@ const MODULUS_LIMBS_32: [u32; 8] = [
 /// INV = -(q^{-1} mod 2^64) mod 2^64
 const INV: u64 = 0x87d20782e4866389;
 
+/// The Montgomery constant `-q^{-1} mod 2^64` used at each step of the
+/// reduction, for code implementing its own arithmetic on `Fq` limbs.
+pub const MONTGOMERY_MODULUS_INV_MOD_R: u64 = INV;
+
 /// R = 2^256 mod q
 const R: Fq = Fq([
     0xd35d438dc58f0d9d,
@@ -72,6 +93,10 @@ const R2: Fq = Fq([
     0x06d89f71cab8351f,
 ]);
 
+/// `R^2 = 2^512 mod q`, stored as raw limbs. A Montgomery multiplication of
+/// canonical limbs by it converts them to Montgomery form.
+pub const MONTGOMERY_R2: Fq = R2;
+
 /// R^3 = 2^768 mod q
 const R3: Fq = Fq([
     0xb1cd6dafda1530df,
@@ -159,6 +184,42 @@ impl Fq {
     pub const fn size() -> usize {
         32
     }
+
+    /// Montgomery reduces the 512-bit integer `T = lo + hi * 2^256`, returning
+    /// the field element whose limbs are `T * 2^-256 mod q`.
+    ///
+    /// This is the last step of a Montgomery multiplication, so the result of
+    /// a custom multiplication of the limbs of two `Fq` elements can be passed
+    /// here unchanged. Requires `T < q * 2^256`, which holds when `hi < q`.
+    pub fn montgomery_reduce_wide(lo: [u64; 4], hi: [u64; 4]) -> Fq {
+        // T * 2^-256 = lo * 2^-256 + hi, and multiplying by the raw limb 1
+        // Montgomery reduces lo
+        Fq(lo).mul(&Fq([1, 0, 0, 0])) + Fq(hi)
+    }
+
+    /// Returns `9 * self`, computed as `8 * self + self` with three doublings
+    /// and an addition.
+    pub fn mul_by_9(&self) -> Fq {
+        self.double().double().double() + self
+    }
+
+    /// Returns whether `self` is zero, in constant time, as a `Choice`.
+    pub fn ct_is_zero(&self) -> Choice {
+        self.ct_eq(&Fq::zero())
+    }
+
+    /// Writes the element-wise products `a[i] * b[i]` into `out`.
+    ///
+    /// Keeping the multiplications of e.g. an NTT butterfly layer in a single
+    /// loop, separate from the additions, leaves room for the compiler to
+    /// interleave them. Panics if the slices differ in length.
+    pub fn batch_mul(a: &[Fq], b: &[Fq], out: &mut [Fq]) {
+        assert_eq!(a.len(), b.len());
+        assert_eq!(a.len(), out.len());
+        for ((out, a), b) in out.iter_mut().zip(a).zip(b) {
+            *out = a * b;
+        }
+    }
 }
 
 extend_field_legendre!(Fq);
@@ -195,8 +256,29 @@ impl ff::Field for Fq {
         CtOption::new(tmp, tmp.square().ct_eq(self))
     }
 
+    /// Computes `sqrt(num / div)` as in RFC 9380, Appendix F.2.1.2, for
+    /// `q = 3 mod 4` and `Z = -1`.
+    ///
+    /// Returns `(1, sqrt(num / div))` if `num / div` is a square, and
+    /// `(0, sqrt(-num / div))` otherwise. This goes through a single
+    /// exponentiation and no inversion. When `num` is zero, returns `(1, 0)`.
+    /// When `div` is zero and `num` is not, returns `(0, 0)`.
     fn sqrt_ratio(num: &Self, div: &Self) -> (Choice, Self) {
-        ff::helpers::sqrt_ratio_generic(num, div)
+        // (q - 3) / 4
+        const C1: [u64; 4] = [
+            0x4f082305b61f3f51,
+            0x65e05aa45a1c72a3,
+            0x6e14116da0605617,
+            0x0c19139cb84c680a,
+        ];
+
+        // num * div^3
+        let tv2 = num * div;
+        let tv1 = div.square() * tv2;
+        // num * div * (num * div^3)^((q - 3) / 4), since sqrt(-Z) = 1
+        let y = tv1.pow(C1) * tv2;
+        let is_square = (y.square() * div).ct_eq(num);
+        (is_square, y)
     }
 
     /// Returns the multiplicative inverse of the
@@ -325,6 +407,35 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_sqrt_ratio() {
+        for _ in 0..1000 {
+            let num = Fq::random(OsRng);
+            let div = Fq::random(OsRng);
+            let (is_square, y) = Fq::sqrt_ratio(&num, &div);
+            let ratio = num * div.invert().unwrap();
+            assert_eq!(bool::from(is_square), bool::from(ratio.sqrt().is_some()));
+            let expected = if bool::from(is_square) { ratio } else { -ratio };
+            assert_eq!(y.square(), expected);
+            assert_eq!((bool::from(is_square), y.square()), {
+                let (is_square, y) = ff::helpers::sqrt_ratio_generic(&num, &div);
+                (bool::from(is_square), y.square())
+            });
+
+            let (is_square, y) = Fq::sqrt_ratio(&num.square(), &div.square());
+            assert!(bool::from(is_square));
+            assert_eq!(y.square() * div.square(), num.square());
+        }
+
+        let a = Fq::random(OsRng);
+        let (is_square, y) = Fq::sqrt_ratio(&Fq::zero(), &a);
+        assert!(bool::from(is_square) && bool::from(y.is_zero()));
+        let (is_square, y) = Fq::sqrt_ratio(&Fq::zero(), &Fq::zero());
+        assert!(bool::from(is_square) && bool::from(y.is_zero()));
+        let (is_square, y) = Fq::sqrt_ratio(&a, &Fq::zero());
+        assert!(!bool::from(is_square) && bool::from(y.is_zero()));
+    }
+
     #[test]
     fn test_from_u512() {
         assert_eq!(
@@ -347,6 +458,128 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_ct_is_zero() {
+        assert!(bool::from(Fq::zero().ct_is_zero()));
+        assert!(!bool::from(Fq::one().ct_is_zero()));
+        assert!(bool::from((-Fq::one() + Fq::one()).ct_is_zero()));
+        for _ in 0..100 {
+            let a = Fq::random(OsRng);
+            assert_eq!(bool::from(a.ct_is_zero()), bool::from(a.is_zero()));
+        }
+    }
+
+    #[test]
+    fn test_eq() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let hash = |a: &Fq| {
+            let mut hasher = DefaultHasher::new();
+            a.hash(&mut hasher);
+            hasher.finish()
+        };
+        for _ in 0..100 {
+            let a = Fq::random(OsRng);
+            let b = Fq::random(OsRng);
+            assert_eq!(a == b, a.0 == b.0);
+            assert_eq!(a == b, bool::from(a.ct_eq(&b)));
+            let c = a + b - b;
+            assert!(a == c && bool::from(a.ct_eq(&c)));
+            assert_eq!(hash(&a), hash(&c));
+            assert!(a != a + Fq::one());
+        }
+    }
+
+    #[test]
+    fn test_from_raw_near_modulus() {
+        let p = MODULUS.0;
+        let mut p_minus_1 = p;
+        p_minus_1[0] -= 1;
+        let mut p_plus_1 = p;
+        p_plus_1[0] += 1;
+
+        assert_eq!(Fq::from_raw(p_minus_1), -Fq::one());
+        assert_eq!(Fq::from_raw(p), Fq::zero());
+        assert_eq!(Fq::from_raw(p_plus_1), Fq::one());
+
+        // The canonical representation is fully reduced
+        let mut p_minus_1_bytes = [0u8; 32];
+        for (chunk, limb) in p_minus_1_bytes.chunks_mut(8).zip(p_minus_1.iter()) {
+            chunk.copy_from_slice(&limb.to_le_bytes());
+        }
+        assert_eq!(Fq::from_raw(p_minus_1).to_repr().as_ref(), p_minus_1_bytes);
+        assert_eq!(Fq::from_raw(p).to_repr().as_ref(), [0u8; 32]);
+        assert_eq!(Fq::from_raw(p_minus_1) + Fq::one(), Fq::zero());
+        assert_eq!(Fq::from_raw(p_minus_1).square(), Fq::one());
+    }
+
+    #[test]
+    fn test_montgomery_reduce_wide() {
+        use crate::arithmetic::mul_512;
+
+        for _ in 0..1000 {
+            let a = Fq::random(OsRng);
+            let b = Fq::random(OsRng);
+            let t = mul_512(a.0, b.0);
+            let (lo, hi) = (t[..4].try_into().unwrap(), t[4..].try_into().unwrap());
+            assert_eq!(Fq::montgomery_reduce_wide(lo, hi), a * b);
+        }
+
+        // Converting canonical limbs to Montgomery form
+        let t = mul_512([5, 0, 0, 0], MONTGOMERY_R2.0);
+        let (lo, hi) = (t[..4].try_into().unwrap(), t[4..].try_into().unwrap());
+        assert_eq!(Fq::montgomery_reduce_wide(lo, hi), Fq::from(5));
+
+        // q * MONTGOMERY_MODULUS_INV_MOD_R = -1 mod 2^64
+        assert_eq!(
+            BN256_P[0].wrapping_mul(MONTGOMERY_MODULUS_INV_MOD_R),
+            u64::MAX
+        );
+    }
+
+    #[test]
+    fn test_mul_by_9() {
+        for _ in 0..100 {
+            let a = Fq::random(OsRng);
+            assert_eq!(a.mul_by_9(), a * Fq::from(9));
+        }
+    }
+
+    #[test]
+    fn test_batch_mul() {
+        let a = (0..100).map(|_| Fq::random(OsRng)).collect::<Vec<_>>();
+        let b = (0..100).map(|_| Fq::random(OsRng)).collect::<Vec<_>>();
+        let mut out = vec![Fq::zero(); 100];
+        Fq::batch_mul(&a, &b, &mut out);
+        for i in 0..100 {
+            assert_eq!(out[i], a[i] * b[i]);
+        }
+        Fq::batch_mul(&[], &[], &mut []);
+    }
+
+    #[test]
+    fn test_modulus_limbs() {
+        let modulus = BN256_P.iter().rev().fold(String::from("0x"), |acc, limb| {
+            acc + &format!("{:016x}", limb)
+        });
+        assert_eq!(modulus, Fq::MODULUS);
+        assert_eq!(Fq::from_raw(BN256_P), Fq::zero());
+    }
+
+    #[test]
+    fn test_invert_fermat() {
+        // Inversion uses the Bernstein-Yang inverter; check it against
+        // a^(q - 2)
+        let mut q_minus_2 = BN256_P;
+        q_minus_2[0] -= 2;
+        assert!(bool::from(Fq::zero().invert().is_none()));
+        for _ in 0..1000 {
+            let a = Fq::random(OsRng);
+            assert_eq!(a.invert().unwrap(), a.pow_vartime(q_minus_2));
+        }
+    }
+
     #[test]
     fn test_field() {
         crate::tests::field::random_field_tests::<Fq>("fq".to_string());
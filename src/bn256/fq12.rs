@@ -1,6 +1,7 @@
 use super::fq::Fq;
 use super::fq2::Fq2;
 use super::fq6::Fq6;
+use crate::arithmetic::{adc, mac, mul_512, sbb};
 use crate::ff::Field;
 use core::ops::{Add, Mul, Neg, Sub};
 use rand::RngCore;
@@ -134,6 +135,75 @@ impl Fq12 {
         self.c0 = c0;
     }
 
+    /// Multiplies with the schoolbook method over `Fq6`, as
+    /// `(a0 b0 + a1 b1 v) + (a0 b1 + a1 b0) w`.
+    ///
+    /// This costs four `Fq6` multiplications against three for the Karatsuba
+    /// method of `mul_assign`, in exchange for fewer additions. It is kept
+    /// as a reference and for benchmarking.
+    pub fn mul_schoolbook(&self, other: &Fq12) -> Fq12 {
+        let mut c0 = self.c1 * other.c1;
+        c0.mul_by_nonresidue();
+        c0 += self.c0 * other.c0;
+        let c1 = self.c0 * other.c1 + self.c1 * other.c0;
+        Fq12 { c0, c1 }
+    }
+
+    /// Multiplies with delayed reduction.
+    ///
+    /// `self` is seen as `sum_i a_i w^i` with `a_i` in `Fq2` and `w^6 = 9 + u`.
+    /// Each output coefficient is then a sum of `Fq` products. These are
+    /// accumulated as unreduced 512-bit integers, and each of the 12 `Fq`
+    /// coefficients is Montgomery reduced only once, instead of once per
+    /// product. That means 144 integer multiplications and 12 reductions,
+    /// against the 54 full `Fq` multiplications of the Karatsuba tower used
+    /// by `mul_assign`. Reductions are cheap relative to the extra
+    /// multiplications on 64-bit targets, and in benchmarks `mul_assign`
+    /// stays faster, so it remains the default.
+    pub fn mul_lazy(&self, other: &Fq12) -> Fq12 {
+        let coeffs = |e: &Fq12| [e.c0.c0, e.c1.c0, e.c0.c1, e.c1.c1, e.c0.c2, e.c1.c2];
+        let (a, b) = (coeffs(self), coeffs(other));
+
+        let mut d = [Fq2::ZERO; 6];
+        for (k, d) in d.iter_mut().enumerate() {
+            // low = sum_{i + j = k} a_i b_j, high = sum_{i + j = k + 6} a_i b_j
+            let mut low = [WideAcc::default(); 2];
+            let mut high = [WideAcc::default(); 2];
+            for (i, a) in a.iter().enumerate() {
+                let (b, acc) = if i <= k {
+                    (&b[k - i], &mut low)
+                } else {
+                    (&b[k + 6 - i], &mut high)
+                };
+                acc[0].add_product(&a.c0, &b.c0, false);
+                acc[0].add_product(&a.c1, &b.c1, true);
+                acc[1].add_product(&a.c0, &b.c1, false);
+                acc[1].add_product(&a.c1, &b.c0, false);
+            }
+
+            // low + (9 + u) * high
+            let [mut c0, mut c1] = low;
+            c0.add_scaled(&high[0], 9, false);
+            c0.add_scaled(&high[1], 1, true);
+            c1.add_scaled(&high[0], 1, false);
+            c1.add_scaled(&high[1], 9, false);
+            *d = Fq2::new(c0.reduce(), c1.reduce());
+        }
+
+        Fq12 {
+            c0: Fq6 {
+                c0: d[0],
+                c1: d[2],
+                c2: d[4],
+            },
+            c1: Fq6 {
+                c0: d[1],
+                c1: d[3],
+                c2: d[5],
+            },
+        }
+    }
+
     pub fn double(&self) -> Self {
         Self {
             c0: self.c0.double(),
@@ -234,6 +304,14 @@ impl Fq12 {
     }
 
     pub fn invert(&self) -> CtOption<Self> {
+        self.invert_via_tower()
+    }
+
+    /// Inverts `self = a + b * w` through the tower,
+    /// `(a + b * w)^-1 = (a - b * w) / (a^2 - b^2 * v)`, so that the only
+    /// inversion performed is that of the `Fq6` norm, which itself reduces
+    /// to a single `Fq2` and in turn a single `Fq` inversion.
+    pub fn invert_via_tower(&self) -> CtOption<Self> {
         let mut c0s = self.c0;
         c0s.square_assign();
         let mut c1s = self.c1;
@@ -251,6 +329,26 @@ impl Fq12 {
         })
     }
 
+    /// Raises `self` to `(q^6 - 1)(q^2 + 1)`, the easy part of the final
+    /// exponentiation, which maps `self` into the cyclotomic subgroup.
+    ///
+    /// `self^(q^6)` is the conjugate of `self`, so the first factor costs a
+    /// conjugation and an inversion, and the second a Frobenius map.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` is zero.
+    pub fn final_exp_easy(&self) -> Fq12 {
+        let mut f = *self;
+        f.conjugate();
+        f.mul_assign(&self.invert().unwrap());
+
+        let mut r = f;
+        r.frobenius_map(2);
+        r.mul_assign(&f);
+        r
+    }
+
     pub fn cyclotomic_square(&mut self) {
         fn fp4_square(c0: &mut Fq2, c1: &mut Fq2, a0: &Fq2, a1: &Fq2) {
             let t0 = a0.square();
@@ -298,6 +396,96 @@ impl Fq12 {
     }
 }
 
+/// A signed sum of products of Montgomery form `Fq` limbs, kept unreduced as
+/// separate positive and negative 576-bit parts.
+#[derive(Clone, Copy, Default)]
+struct WideAcc {
+    pos: [u64; 9],
+    neg: [u64; 9],
+}
+
+impl WideAcc {
+    fn add_limbs(acc: &mut [u64; 9], x: &[u64]) {
+        let mut carry = 0;
+        for (i, limb) in acc.iter_mut().enumerate() {
+            (*limb, carry) = adc(*limb, x.get(i).copied().unwrap_or(0), carry);
+        }
+        debug_assert_eq!(carry, 0);
+    }
+
+    fn add_product(&mut self, a: &Fq, b: &Fq, negate: bool) {
+        let product = mul_512(a.0, b.0);
+        Self::add_limbs(if negate { &mut self.neg } else { &mut self.pos }, &product);
+    }
+
+    fn add_scaled(&mut self, other: &WideAcc, k: u64, negate: bool) {
+        let scale = |x: &[u64; 9]| {
+            let mut res = [0u64; 9];
+            let mut carry = 0;
+            for (r, limb) in res.iter_mut().zip(x.iter()) {
+                (*r, carry) = mac(0, *limb, k, carry);
+            }
+            debug_assert_eq!(carry, 0);
+            res
+        };
+        let (pos, neg) = if negate {
+            (&other.neg, &other.pos)
+        } else {
+            (&other.pos, &other.neg)
+        };
+        Self::add_limbs(&mut self.pos, &scale(pos));
+        Self::add_limbs(&mut self.neg, &scale(neg));
+    }
+
+    /// Returns `(pos - neg) / 2^256 mod q`.
+    ///
+    /// In `mul_lazy` both parts stay below `126 q^2 < 24 q 2^256`, so adding
+    /// `32 q 2^256` makes the difference nonnegative and its top 320 bits
+    /// smaller than `64 q`, which a few conditional subtractions bring
+    /// below `q` for the Montgomery reduction.
+    fn reduce(&self) -> Fq {
+        const MODULUS: [u64; 4] = super::fq::BN256_P;
+
+        let mut t = self.pos;
+        let mut offset = [0u64; 9];
+        let mut carry = 0;
+        for i in 0..4 {
+            (offset[i + 4], carry) = mac(0, MODULUS[i], 32, carry);
+        }
+        offset[8] = carry;
+        Self::add_limbs(&mut t, &offset);
+        let mut borrow = 0;
+        for (limb, neg) in t.iter_mut().zip(self.neg.iter()) {
+            (*limb, borrow) = sbb(*limb, *neg, borrow);
+        }
+        debug_assert_eq!(borrow, 0);
+
+        let mut hi = [t[4], t[5], t[6], t[7], t[8]];
+        for shift in (0..6).rev() {
+            let mut multiple = [0u64; 5];
+            let mut carry = 0;
+            for i in 0..4 {
+                (multiple[i], carry) = mac(0, MODULUS[i], 1 << shift, carry);
+            }
+            multiple[4] = carry;
+
+            let mut diff = [0u64; 5];
+            let mut borrow = 0;
+            for i in 0..5 {
+                (diff[i], borrow) = sbb(hi[i], multiple[i], borrow);
+            }
+            // keep the difference unless it underflowed
+            let keep = Choice::from((borrow & 1) as u8);
+            for i in 0..5 {
+                hi[i] = u64::conditional_select(&diff[i], &hi[i], keep);
+            }
+        }
+        debug_assert_eq!(hi[4], 0);
+
+        Fq::montgomery_reduce_wide([t[0], t[1], t[2], t[3]], [hi[0], hi[1], hi[2], hi[3]])
+    }
+}
+
 impl Field for Fq12 {
     const ZERO: Self = Self::zero();
     const ONE: Self = Self::one();
@@ -558,6 +746,89 @@ fn test_fq12_mul_by_034() {
     }
 }
 
+#[test]
+fn test_invert_via_tower() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    assert!(bool::from(Fq12::zero().invert_via_tower().is_none()));
+    assert_eq!(Fq12::one().invert_via_tower().unwrap(), Fq12::one());
+
+    for _ in 0..1000 {
+        let a = Fq12::random(&mut rng);
+        let a_inv = a.invert_via_tower().unwrap();
+        assert_eq!(a * a_inv, Fq12::one());
+        assert_eq!(a_inv.invert_via_tower().unwrap(), a);
+
+        // Only the Fq6 component c0 is nonzero
+        let b = Fq12 {
+            c0: a.c0,
+            c1: Fq6::zero(),
+        };
+        assert_eq!(b * b.invert_via_tower().unwrap(), Fq12::one());
+    }
+}
+
+#[test]
+fn test_final_exp_easy() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for _ in 0..100 {
+        let a = Fq12::random(&mut rng);
+        let f = a.final_exp_easy();
+
+        // Elements of the cyclotomic subgroup have their conjugate as inverse
+        let mut f_conj = f;
+        f_conj.conjugate();
+        assert_eq!(f * f_conj, Fq12::one());
+        assert_eq!(f.invert().unwrap(), f_conj);
+
+        let mut f_sq = f;
+        f_sq.cyclotomic_square();
+        assert_eq!(f_sq, f.square());
+
+        // a^(q^6 - 1)(q^2 + 1) computed the long way
+        let mut a_q6 = a;
+        a_q6.frobenius_map(6);
+        let t = a_q6 * a.invert().unwrap();
+        let mut t_q2 = t;
+        t_q2.frobenius_map(2);
+        assert_eq!(t_q2 * t, f);
+    }
+}
+
+#[test]
+fn test_mul_variants() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for _ in 0..1000 {
+        let a = Fq12::random(&mut rng);
+        let b = Fq12::random(&mut rng);
+        assert_eq!(a.mul_schoolbook(&b), a * b);
+        assert_eq!(a.mul_lazy(&b), a * b);
+    }
+
+    // Coefficients as large as possible
+    let max = Fq2::new(-Fq::one(), -Fq::one());
+    let max6 = Fq6 {
+        c0: max,
+        c1: max,
+        c2: max,
+    };
+    let a = Fq12 { c0: max6, c1: max6 };
+    assert_eq!(a.mul_lazy(&a), a * a);
+    assert_eq!(a.mul_lazy(&Fq12::ONE), a);
+    assert_eq!(a.mul_lazy(&Fq12::ZERO), Fq12::ZERO);
+}
+
 #[test]
 fn test_squaring() {
     let mut rng = XorShiftRng::from_seed([
@@ -601,6 +872,12 @@ fn test_frobenius() {
     }
 }
 
+#[test]
+fn test_frobenius_order() {
+    // The Frobenius endomorphism has order 12 on Fq12
+    crate::tests::field::frobenius_order_test::<Fq12>(12, Fq12::frobenius_map);
+}
+
 #[test]
 fn test_field() {
     crate::tests::field::random_field_tests::<Fq12>("fq12".to_string());
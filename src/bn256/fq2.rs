@@ -11,7 +11,7 @@ use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
 use serde::{Deserialize, Serialize};
 
 /// An element of Fq2, represented by c0 + c1 * u; where u^2 = -1.
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq)]
 #[cfg_attr(feature = "derive_serde", derive(Serialize, Deserialize))]
 pub struct Fq2 {
     pub c0: Fq,
@@ -46,12 +46,20 @@ impl ConditionallySelectable for Fq2 {
     }
 }
 
+/// Both coefficients are always compared, in constant time.
 impl ConstantTimeEq for Fq2 {
     fn ct_eq(&self, other: &Self) -> Choice {
         self.c0.ct_eq(&other.c0) & self.c1.ct_eq(&other.c1)
     }
 }
 
+/// Equality is checked in constant time, through [`ConstantTimeEq`].
+impl PartialEq for Fq2 {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
 impl Default for Fq2 {
     #[inline]
     fn default() -> Self {
@@ -253,26 +261,25 @@ impl Fq2 {
         }
     }
 
+    /// Returns the quadratic nonresidue `9 + u`, which is also the cubic
+    /// nonresidue the `Fq6` tower is built upon.
+    pub const fn mul_by_nonresidue_constant() -> Fq2 {
+        Fq2 {
+            c0: Fq::from_raw([9, 0, 0, 0]),
+            c1: Fq::one(),
+        }
+    }
+
     /// Multiply this element by quadratic nonresidue 9 + u.
     pub fn mul_by_nonresidue(&mut self) {
         // (xu+y)(u+9) = (9x+y)u+(9y-x)
         let t0 = self.c0;
         let t1 = self.c1;
 
-        // 8*x*i + 8*y
-        self.double_assign();
-        self.double_assign();
-        self.double_assign();
-
-        // 9*y
-        self.c0 += &t0;
         // (9*y - x)
-        self.c0 -= &t1;
-
-        // (9*x)u
-        self.c1 += &t1;
-        // (9*x + y)
-        self.c1 += &t0;
+        self.c0 = t0.mul_by_9() - t1;
+        // (9*x + y)u
+        self.c1 = t1.mul_by_9() + t0;
     }
 
     pub fn invert(&self) -> CtOption<Self> {
@@ -303,6 +310,24 @@ impl Fq2 {
         t1 = t1.square();
         t1 + t0
     }
+
+    /// Returns whether `self` is a square in `Fq2`, in constant time. Zero is
+    /// considered a square.
+    ///
+    /// An element of a quadratic extension is a square exactly when its norm
+    /// `c0^2 + c1^2` is a square in `Fq`, so this runs Euler's criterion in
+    /// `Fq` rather than exponentiating by `(q^2 - 1) / 2` in `Fq2`.
+    pub fn is_square(&self) -> Choice {
+        // (q - 1) / 2
+        const Q_MINUS_1_OVER_2: [u64; 4] = [
+            0x9e10460b6c3e7ea3,
+            0xcbc0b548b438e546,
+            0xdc2822db40c0ac2e,
+            0x183227397098d014,
+        ];
+        let norm = self.norm();
+        norm.is_zero() | norm.pow(Q_MINUS_1_OVER_2).ct_eq(&Fq::ONE)
+    }
 }
 
 impl Legendre for Fq2 {
@@ -461,8 +486,10 @@ impl PrimeField for Fq2 {
         Fq2Bytes(self.to_bytes())
     }
 
+    /// Returns `sgn0(self)` as defined in RFC 9380: the parity of `c0`, or
+    /// the parity of `c1` when `c0` is zero.
     fn is_odd(&self) -> Choice {
-        Choice::from(self.to_repr().as_ref()[0] & 1)
+        self.c0.is_odd() | (self.c0.is_zero() & self.c1.is_odd())
     }
 }
 
@@ -582,6 +609,82 @@ fn test_fq2_ordering() {
     assert!(a.cmp(&b) == Ordering::Equal);
 }
 
+#[test]
+fn test_fq2_conditional_select() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for _ in 0..100 {
+        let a = Fq2::random(&mut rng);
+        let b = Fq2::random(&mut rng);
+
+        assert_eq!(Fq2::conditional_select(&a, &b, Choice::from(0)), a);
+        assert_eq!(Fq2::conditional_select(&a, &b, Choice::from(1)), b);
+
+        // Each component is selected independently
+        let c = Fq2::new(a.c0, b.c1);
+        assert_eq!(Fq2::conditional_select(&a, &c, Choice::from(1)).c0, a.c0);
+        assert_eq!(Fq2::conditional_select(&a, &c, Choice::from(1)).c1, b.c1);
+    }
+}
+
+#[test]
+fn test_fq2_conditional_ops() {
+    use subtle::ConditionallyNegatable;
+
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    // The operations subtle derives from `ConditionallySelectable`
+    for _ in 0..100 {
+        let a = Fq2::random(&mut rng);
+        let b = Fq2::random(&mut rng);
+
+        for choice in [0, 1] {
+            let choice = Choice::from(choice);
+            let pick = |x: Fq2, y: Fq2| if bool::from(choice) { y } else { x };
+
+            let mut c = a;
+            c.conditional_assign(&b, choice);
+            assert_eq!(c, pick(a, b));
+
+            let (mut x, mut y) = (a, b);
+            Fq2::conditional_swap(&mut x, &mut y, choice);
+            assert_eq!((x, y), (pick(a, b), pick(b, a)));
+
+            let mut c = a;
+            c.conditional_negate(choice);
+            assert_eq!(c, pick(a, -a));
+        }
+    }
+}
+
+#[test]
+fn test_fq2_ct_eq() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    for _ in 0..100 {
+        let a = Fq2::random(&mut rng);
+        let b = Fq2::random(&mut rng);
+        assert!(bool::from(a.ct_eq(&a)));
+        assert_eq!(bool::from(a.ct_eq(&b)), a.c0 == b.c0 && a.c1 == b.c1);
+        assert_eq!(a == b, bool::from(a.ct_eq(&b)));
+
+        // Differing in only one coefficient
+        let c0_only = Fq2::new(a.c0, b.c1);
+        let c1_only = Fq2::new(b.c0, a.c1);
+        assert!(!bool::from(a.ct_eq(&c0_only)) && a != c0_only);
+        assert!(!bool::from(a.ct_eq(&c1_only)) && a != c1_only);
+    }
+}
+
 #[test]
 fn test_fq2_basics() {
     assert_eq!(
@@ -640,6 +743,33 @@ fn test_fq2_squaring() {
     }); // -1
 }
 
+#[test]
+fn test_tower_nonresidues() {
+    use crate::bn256::BN256_P;
+    use num_bigint::BigUint;
+
+    // Fq2 = Fq[u] / (u^2 + 1) requires -1 to be a non-square in Fq.
+    assert_eq!((-Fq::one()).legendre(), -1);
+    let u = Fq2 {
+        c0: Fq::zero(),
+        c1: Fq::one(),
+    };
+    assert_eq!(u.square(), -Fq2::one());
+
+    // Fq6 = Fq2[v] / (v^3 - xi) requires xi = 9 + u to be a non-cube in Fq2,
+    // i.e. xi^((q^2 - 1) / 3) != 1. Fq12 = Fq6[w] / (w^2 - v) additionally
+    // uses that xi is a non-square.
+    let q = BN256_P
+        .iter()
+        .rev()
+        .fold(BigUint::from(0u64), |acc, limb| (acc << 64) + *limb);
+    let order = &q * &q - 1u64;
+    let xi = Fq2::mul_by_nonresidue_constant();
+    assert_ne!(xi.pow_vartime((&order / 3u64).to_u64_digits()), Fq2::one());
+    assert_ne!(xi.pow_vartime((&order / 2u64).to_u64_digits()), Fq2::one());
+    assert_eq!(xi.legendre(), -1);
+}
+
 #[test]
 fn test_fq2_mul_nonresidue() {
     let mut rng = XorShiftRng::from_seed([
@@ -651,6 +781,8 @@ fn test_fq2_mul_nonresidue() {
         c0: nine,
         c1: Fq::one(),
     };
+    assert_eq!(Fq2::mul_by_nonresidue_constant(), nqr);
+    assert_eq!(nqr.legendre(), -1);
 
     for _ in 0..1000 {
         let mut a = Fq2::random(&mut rng);
@@ -707,6 +839,29 @@ pub fn test_sqrt() {
     }
 }
 
+#[test]
+fn test_is_square() {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    assert!(bool::from(Fq2::ZERO.is_square()));
+    assert!(bool::from(Fq2::ONE.is_square()));
+    // Every element of Fq is a square in Fq2, as is u
+    assert!(bool::from(Fq2::new(-Fq::ONE, Fq::ZERO).is_square()));
+    assert!(bool::from(Fq2::new(Fq::ZERO, Fq::ONE).is_square()));
+    // The non-residue used to build Fq6
+    assert!(!bool::from(Fq2::new(Fq::from(9), Fq::ONE).is_square()));
+
+    for _ in 0..1000 {
+        let a = Fq2::random(&mut rng);
+        assert!(bool::from(a.square().is_square()));
+        assert_eq!(bool::from(a.is_square()), bool::from(a.sqrt().is_some()));
+        assert_eq!(bool::from(a.is_square()), a.legendre() != -1);
+    }
+}
+
 #[test]
 fn test_frobenius() {
     let mut rng = XorShiftRng::from_seed([
@@ -734,6 +889,12 @@ fn test_frobenius() {
     }
 }
 
+#[test]
+fn test_frobenius_order() {
+    // The Frobenius endomorphism has order 2 on Fq2
+    crate::tests::field::frobenius_order_test::<Fq2>(2, Fq2::frobenius_map);
+}
+
 #[test]
 fn test_zeta() {
     let zeta = Fq2::new(Fq::ZETA.square(), Fq::zero());
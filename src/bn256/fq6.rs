@@ -703,6 +703,12 @@ fn test_frobenius() {
     }
 }
 
+#[test]
+fn test_frobenius_order() {
+    // The Frobenius endomorphism has order 6 on Fq6
+    crate::tests::field::frobenius_order_test::<Fq6>(6, Fq6::frobenius_map);
+}
+
 #[test]
 fn test_field() {
     crate::tests::field::random_field_tests::<Fq6>("fq6".to_string());
@@ -20,7 +20,7 @@ use crate::impl_from_u64;
 
 use crate::arithmetic::{adc, mac, sbb};
 use crate::extend_field_legendre;
-use crate::ff::{FromUniformBytes, PrimeField, WithSmallOrderMulGroup};
+use crate::ff::{BatchInvert, FromUniformBytes, PrimeField, WithSmallOrderMulGroup};
 use crate::{
     field_bits, field_common, impl_add_binop_specify_output, impl_binops_additive,
     impl_binops_additive_specify_output, impl_binops_multiplicative,
@@ -29,6 +29,8 @@ use crate::{
 use core::convert::TryInto;
 use core::fmt;
 use core::ops::{Add, Mul, Neg, Sub};
+use num_bigint::{BigInt, Sign};
+use num_traits::{One, Zero};
 use rand::RngCore;
 use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
 
@@ -40,20 +42,37 @@ use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
 // The internal representation of this type is four 64-bit unsigned
 // integers in little-endian order. `Fr` values are always in
 // Montgomery form; i.e., Fr(a) = aR mod r, with R = 2^256.
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Eq)]
 pub struct Fr(pub(crate) [u64; 4]);
 
+/// Equality is checked in constant time, through [`ConstantTimeEq`].
+impl PartialEq for Fr {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl core::hash::Hash for Fr {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
 #[cfg(feature = "derive_serde")]
 crate::serialize_deserialize_32_byte_primefield!(Fr);
 
-/// Constant representing the modulus
+/// The order of the BN256 groups in little-endian 64-bit limbs
 /// r = 0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001
-const MODULUS: Fr = Fr([
+pub const BN256_R: [u64; 4] = [
     0x43e1f593f0000001,
     0x2833e84879b97091,
     0xb85045b68181585d,
     0x30644e72e131a029,
-]);
+];
+
+/// Constant representing the modulus
+/// r = 0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001
+const MODULUS: Fr = Fr(BN256_R);
 
 /// The modulus as u32 limbs.
 #[cfg(not(target_pointer_width = "64"))]
@@ -204,6 +223,189 @@ impl Fr {
     pub const fn size() -> usize {
         32
     }
+
+    /// Returns whether `self` is zero, in constant time, as a `Choice`.
+    pub fn ct_is_zero(&self) -> Choice {
+        self.ct_eq(&Fr::zero())
+    }
+
+    /// Exponentiates `self` by `t`, given as little-endian `u64` limbs of any
+    /// length, by delegating to [`ff::Field::pow_vartime`].
+    ///
+    /// This is variable time with respect to the exponent.
+    pub fn pow_by_t(&self, t: &[u64]) -> Fr {
+        ff::Field::pow_vartime(self, t)
+    }
+
+    /// Samples a uniformly distributed element by rejection sampling.
+    ///
+    /// Draws 256 bits from `rng`, clears the top two (the modulus is below
+    /// `2^254`) and retries until the integer is smaller than `r`. Every
+    /// accepted value is equally likely, so there is no modular bias. Each
+    /// draw is accepted with probability `r / 2^254 ~ 0.756`, giving about
+    /// 1.32 draws on average.
+    ///
+    /// The number of draws depends on the random values, but not on the
+    /// element that is eventually returned.
+    pub fn random_uniform(rng: &mut impl RngCore) -> Fr {
+        loop {
+            let mut limbs = [
+                rng.next_u64(),
+                rng.next_u64(),
+                rng.next_u64(),
+                rng.next_u64(),
+            ];
+            limbs[3] &= u64::MAX >> 2;
+            if limbs.iter().rev().lt(BN256_R.iter().rev()) {
+                return Fr::from_raw(limbs);
+            }
+        }
+    }
+
+    /// Encodes `self` for absorption into a BLAKE2 transcript.
+    ///
+    /// This is the canonical little-endian encoding, identical to
+    /// [`PrimeField::to_repr`]. It never exposes the internal Montgomery form.
+    pub fn to_blake2_input(&self) -> [u8; 32] {
+        self.to_repr()
+    }
+
+    /// Decodes a 32-byte BLAKE2 digest into a scalar.
+    ///
+    /// The digest is read as a little-endian integer and reduced modulo `r`,
+    /// so every digest maps to a field element. The result is far from
+    /// uniform: `2^256 / r ~ 5.29`, so the residues below `2^256 mod r`
+    /// (about 29% of the field) have six preimages while all others have
+    /// five, making them 20% more likely. Do not use this to derive secrets,
+    /// nonces or challenges; hash to a 64-byte digest and use
+    /// [`FromUniformBytes::from_uniform_bytes`] instead.
+    pub fn from_blake2_output(hash: &[u8; 32]) -> Fr {
+        let mut wide = [0u8; 64];
+        wide[..32].copy_from_slice(hash);
+        Fr::from_uniform_bytes(&wide)
+    }
+
+    /// Runs the extended Euclidean algorithm on the canonical integer
+    /// representatives of `a` and `b`, returning `(g, x, y)` such that
+    /// `a * x + b * y = g` where `g = gcd(a, b)`. The Bezout coefficients,
+    /// which may be negative over the integers, are reduced modulo `r`.
+    ///
+    /// This is variable time and must only be used on public values.
+    pub fn extended_gcd(a: &Fr, b: &Fr) -> (Fr, Fr, Fr) {
+        let (g, x, y) = extended_gcd_bigint(a.to_bigint(), b.to_bigint());
+        (
+            Fr::from_bigint(&g),
+            Fr::from_bigint(&x),
+            Fr::from_bigint(&y),
+        )
+    }
+
+    /// Returns the inverse of `a` modulo the integer represented by `n`,
+    /// that is `x < n` such that `a * x = 1 mod n`, or `None` if `a` and `n`
+    /// are not coprime or `n` is zero.
+    ///
+    /// This is variable time and must only be used on public values.
+    pub fn bezout_coefficients(a: &Fr, n: &Fr) -> Option<Fr> {
+        let n = n.to_bigint();
+        if n.is_zero() {
+            return None;
+        }
+        let (g, x, _) = extended_gcd_bigint(a.to_bigint() % &n, n.clone());
+        if !g.is_one() {
+            return None;
+        }
+        let x = ((x % &n) + &n) % &n;
+        Some(Fr::from_bigint(&x))
+    }
+
+    /// Returns the coefficients, lowest degree first, of the unique
+    /// polynomial of degree below `xs.len()` that takes the value `ys[i]` at
+    /// `xs[i]` for every `i`.
+    ///
+    /// Uses the Lagrange formula in `O(n^2)` operations: the vanishing
+    /// polynomial `Z(X) = prod (X - x_k)` is built once, and each basis
+    /// polynomial is obtained from it by a synthetic division by `X - x_j`.
+    ///
+    /// Panics if `xs` and `ys` differ in length or if `xs` contains repeated
+    /// points.
+    pub fn lagrange_interpolate(xs: &[Fr], ys: &[Fr]) -> Vec<Fr> {
+        assert_eq!(xs.len(), ys.len());
+        let n = xs.len();
+
+        // Z(X), with the leading coefficient at index n.
+        let mut vanishing = vec![Fr::zero(); n + 1];
+        vanishing[0] = Fr::one();
+        for (k, x) in xs.iter().enumerate() {
+            for i in (1..=k + 1).rev() {
+                vanishing[i] = vanishing[i - 1] - vanishing[i] * x;
+            }
+            vanishing[0] = -vanishing[0] * x;
+        }
+
+        let mut weights = xs
+            .iter()
+            .enumerate()
+            .map(|(j, xj)| {
+                xs.iter()
+                    .enumerate()
+                    .filter(|(k, _)| *k != j)
+                    .fold(Fr::one(), |acc, (_, xk)| acc * (xj - xk))
+            })
+            .collect::<Vec<_>>();
+        assert!(
+            weights.iter().all(|w| !ff::Field::is_zero_vartime(w)),
+            "interpolation points must be distinct"
+        );
+        weights.iter_mut().batch_invert();
+
+        let mut coeffs = vec![Fr::zero(); n];
+        let mut basis = vec![Fr::zero(); n];
+        for ((xj, yj), wj) in xs.iter().zip(ys).zip(weights) {
+            // Z(X) / (X - x_j), from the top coefficient down.
+            let mut carry = Fr::zero();
+            for i in (0..n).rev() {
+                carry = vanishing[i + 1] + carry * xj;
+                basis[i] = carry;
+            }
+            let scale = wj * yj;
+            for (c, b) in coeffs.iter_mut().zip(basis.iter()) {
+                *c += *b * scale;
+            }
+        }
+        coeffs
+    }
+
+    fn to_bigint(self) -> BigInt {
+        BigInt::from_bytes_le(Sign::Plus, &self.to_repr())
+    }
+
+    /// Reduces a signed integer whose magnitude is below the modulus.
+    fn from_bigint(v: &BigInt) -> Fr {
+        let (sign, bytes) = v.to_bytes_le();
+        let mut repr = [0u8; 32];
+        repr[..bytes.len()].copy_from_slice(&bytes);
+        let v = Fr::from_repr(repr).unwrap();
+        if sign == Sign::Minus {
+            -v
+        } else {
+            v
+        }
+    }
+}
+
+fn extended_gcd_bigint(a: BigInt, b: BigInt) -> (BigInt, BigInt, BigInt) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_s, mut s) = (BigInt::one(), BigInt::zero());
+    let (mut old_t, mut t) = (BigInt::zero(), BigInt::one());
+
+    while !r.is_zero() {
+        let q = &old_r / &r;
+        (old_r, r) = (r.clone(), old_r - &q * r);
+        (old_s, s) = (s.clone(), old_s - &q * s);
+        (old_t, t) = (t.clone(), old_t - &q * t);
+    }
+
+    (old_r, old_s, old_t)
 }
 
 impl ff::Field for Fr {
@@ -359,6 +561,71 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_ct_is_zero() {
+        assert!(bool::from(Fr::zero().ct_is_zero()));
+        assert!(!bool::from(Fr::one().ct_is_zero()));
+        assert!(bool::from((-Fr::one() + Fr::one()).ct_is_zero()));
+        for _ in 0..100 {
+            let a = Fr::random(OsRng);
+            assert_eq!(bool::from(a.ct_is_zero()), bool::from(a.is_zero()));
+        }
+    }
+
+    #[test]
+    fn test_eq() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let hash = |a: &Fr| {
+            let mut hasher = DefaultHasher::new();
+            a.hash(&mut hasher);
+            hasher.finish()
+        };
+        for _ in 0..100 {
+            let a = Fr::random(OsRng);
+            let b = Fr::random(OsRng);
+            assert_eq!(a == b, a.0 == b.0);
+            assert_eq!(a == b, bool::from(a.ct_eq(&b)));
+            let c = a + b - b;
+            assert!(a == c && bool::from(a.ct_eq(&c)));
+            assert_eq!(hash(&a), hash(&c));
+            assert!(a != a + Fr::one());
+        }
+    }
+
+    #[test]
+    fn test_from_raw_near_modulus() {
+        let p = MODULUS.0;
+        let mut p_minus_1 = p;
+        p_minus_1[0] -= 1;
+        let mut p_plus_1 = p;
+        p_plus_1[0] += 1;
+
+        assert_eq!(Fr::from_raw(p_minus_1), -Fr::one());
+        assert_eq!(Fr::from_raw(p), Fr::zero());
+        assert_eq!(Fr::from_raw(p_plus_1), Fr::one());
+
+        // The canonical representation is fully reduced
+        let mut p_minus_1_bytes = [0u8; 32];
+        for (chunk, limb) in p_minus_1_bytes.chunks_mut(8).zip(p_minus_1.iter()) {
+            chunk.copy_from_slice(&limb.to_le_bytes());
+        }
+        assert_eq!(Fr::from_raw(p_minus_1).to_repr().as_ref(), p_minus_1_bytes);
+        assert_eq!(Fr::from_raw(p).to_repr().as_ref(), [0u8; 32]);
+        assert_eq!(Fr::from_raw(p_minus_1) + Fr::one(), Fr::zero());
+        assert_eq!(Fr::from_raw(p_minus_1).square(), Fr::one());
+    }
+
+    #[test]
+    fn test_modulus_limbs() {
+        let modulus = BN256_R.iter().rev().fold(String::from("0x"), |acc, limb| {
+            acc + &format!("{:016x}", limb)
+        });
+        assert_eq!(modulus, Fr::MODULUS);
+        assert_eq!(Fr::from_raw(BN256_R), Fr::zero());
+    }
+
     #[test]
     fn test_field() {
         crate::tests::field::random_field_tests::<Fr>("bn256 scalar".to_string());
@@ -465,6 +732,177 @@ mod test {
         end_timer!(timer);
     }
 
+    #[test]
+    fn test_pow_by_t() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        for _ in 0..100 {
+            let a = Fr::random(&mut rng);
+            assert_eq!(a.pow_by_t(&[]), Fr::one());
+            assert_eq!(a.pow_by_t(&[0, 0]), Fr::one());
+            assert_eq!(a.pow_by_t(&[1]), a);
+            assert_eq!(a.pow_by_t(&[5, 0, 0]), a.square().square() * a);
+
+            let e = [
+                rng.next_u64(),
+                rng.next_u64(),
+                rng.next_u64(),
+                rng.next_u64(),
+            ];
+            assert_eq!(a.pow_by_t(&e), a.pow_vartime(e));
+
+            // a^(r - 1) = 1 for nonzero a
+            let r_minus_1 = (-Fr::one()).to_repr();
+            let limbs: Vec<u64> = r_minus_1
+                .chunks(8)
+                .map(|c| u64::from_le_bytes(c.try_into().unwrap()))
+                .collect();
+            assert_eq!(a.pow_by_t(&limbs), Fr::one());
+        }
+    }
+
+    #[test]
+    fn test_lagrange_interpolate() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        let eval =
+            |coeffs: &[Fr], x: Fr| coeffs.iter().rev().fold(Fr::zero(), |acc, c| acc * x + c);
+
+        assert!(Fr::lagrange_interpolate(&[], &[]).is_empty());
+        for n in 1..20 {
+            let poly = (0..n).map(|_| Fr::random(&mut rng)).collect::<Vec<_>>();
+            let xs = (0..n).map(|_| Fr::random(&mut rng)).collect::<Vec<_>>();
+            let ys = xs.iter().map(|x| eval(&poly, *x)).collect::<Vec<_>>();
+            assert_eq!(Fr::lagrange_interpolate(&xs, &ys), poly);
+        }
+
+        // Shamir style reconstruction: the secret is the constant term.
+        let xs = [Fr::from(1), Fr::from(2), Fr::from(3)];
+        let ys = [Fr::from(6), Fr::from(11), Fr::from(18)];
+        assert_eq!(
+            Fr::lagrange_interpolate(&xs, &ys),
+            vec![Fr::from(3), Fr::from(2), Fr::from(1)]
+        );
+    }
+
+    #[test]
+    fn test_blake2_encoding() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        for _ in 0..100 {
+            let a = Fr::random(&mut rng);
+            let bytes = a.to_blake2_input();
+            assert_eq!(bytes, a.to_repr());
+            assert_eq!(Fr::from_blake2_output(&bytes), a);
+        }
+
+        assert_eq!(Fr::one().to_blake2_input()[0], 1);
+        assert!(Fr::one().to_blake2_input()[1..].iter().all(|b| *b == 0));
+
+        // Digests at or above the modulus are reduced.
+        let mut modulus = [0u8; 32];
+        for (chunk, limb) in modulus.chunks_mut(8).zip(BN256_R.iter()) {
+            chunk.copy_from_slice(&limb.to_le_bytes());
+        }
+        assert_eq!(Fr::from_blake2_output(&modulus), Fr::zero());
+        assert_eq!(
+            Fr::from_blake2_output(&[0xff; 32]),
+            Fr::from_raw([u64::MAX; 4])
+        );
+    }
+
+    #[test]
+    fn test_random_uniform() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        // Split [0, r) into 16 equal intervals and run a chi-squared test on
+        // the number of samples falling in each of them.
+        const SAMPLES: usize = 10000;
+        const BUCKETS: usize = 16;
+        let modulus = (-Fr::one()).to_bigint() + 1;
+        let mut counts = [0usize; BUCKETS];
+        for _ in 0..SAMPLES {
+            let a = Fr::random_uniform(&mut rng);
+            let bucket: BigInt = a.to_bigint() * BUCKETS / &modulus;
+            counts[bucket.to_u64_digits().1.first().copied().unwrap_or(0) as usize] += 1;
+        }
+
+        let expected = (SAMPLES / BUCKETS) as f64;
+        let chi_squared: f64 = counts
+            .iter()
+            .map(|&c| (c as f64 - expected).powi(2) / expected)
+            .sum();
+        // 99.9th percentile of the chi-squared distribution with 15 degrees
+        // of freedom
+        assert!(chi_squared < 37.7, "chi-squared statistic {chi_squared}");
+
+        // Elements are always canonical
+        for _ in 0..100 {
+            let a = Fr::random_uniform(&mut rng);
+            assert_eq!(Fr::from_repr(a.to_repr()).unwrap(), a);
+        }
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        for _ in 0..1000 {
+            let a = Fr::random(&mut rng);
+            let b = Fr::random(&mut rng);
+            let (g, x, y) = Fr::extended_gcd(&a, &b);
+            assert_eq!(a * x + b * y, g);
+        }
+
+        let (g, x, y) = Fr::extended_gcd(&Fr::from(240), &Fr::from(46));
+        assert_eq!(g, Fr::from(2));
+        assert_eq!(x, -Fr::from(9));
+        assert_eq!(y, Fr::from(47));
+
+        let (g, _, _) = Fr::extended_gcd(&Fr::from(12), &Fr::zero());
+        assert_eq!(g, Fr::from(12));
+    }
+
+    #[test]
+    fn test_bezout_coefficients() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        // 2^61 - 1 is prime, so every non-multiple has an inverse
+        let n = 0x1fffffffffffffffu64;
+        for _ in 0..1000 {
+            let a = rng.next_u64() % n;
+            let x = Fr::bezout_coefficients(&Fr::from(a), &Fr::from(n));
+            if a == 0 {
+                assert!(x.is_none());
+                continue;
+            }
+            let x = x.unwrap().to_repr();
+            let x = u64::from_le_bytes(x[..8].try_into().unwrap());
+            assert!(x < n);
+            assert_eq!((a as u128 * x as u128) % n as u128, 1);
+        }
+
+        assert_eq!(
+            Fr::bezout_coefficients(&Fr::from(3), &Fr::from(7)),
+            Some(Fr::from(5))
+        );
+        assert_eq!(Fr::bezout_coefficients(&Fr::from(4), &Fr::from(8)), None);
+        assert_eq!(Fr::bezout_coefficients(&Fr::from(4), &Fr::zero()), None);
+    }
+
     #[test]
     fn test_quadratic_residue() {
         crate::tests::field::random_quadratic_residue_test::<Fr>();
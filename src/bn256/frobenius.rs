@@ -0,0 +1,93 @@
+//! Derivation of the Frobenius coefficients of the BN256 extension tower.
+//!
+//! The Frobenius maps in `Fq2`, `Fq6` and `Fq12` use hardcoded coefficient
+//! tables. This module recomputes those tables from the base field modulus and
+//! the tower non-residues so that the constants can be cross-checked. It is
+//! never needed at runtime: it is exported with the `frobenius-coeffs`
+//! feature, and always compiled for tests so the check runs by default.
+
+use super::{Fq, Fq2};
+use ff::Field;
+use num_bigint::BigUint;
+
+/// Frobenius coefficients of the BN256 tower, indexed by the power of the
+/// Frobenius map.
+#[derive(Clone, Debug)]
+pub struct FrobeniusCoeffs {
+    /// `β^((p^i - 1) / 2)` where `u² = β`.
+    pub fq2_c1: [Fq; 2],
+    /// `ξ^((p^i - 1) / 3)` where `v³ = ξ`.
+    pub fq6_c1: [Fq2; 6],
+    /// `ξ^((2p^i - 2) / 3)` where `v³ = ξ`.
+    pub fq6_c2: [Fq2; 6],
+    /// `ξ^((p^i - 1) / 6)` where `w⁶ = ξ`.
+    pub fq12_c1: [Fq2; 12],
+}
+
+fn pow_biguint<F: Field>(base: F, exp: &BigUint) -> F {
+    base.pow_vartime(exp.to_u64_digits())
+}
+
+/// Computes the Frobenius coefficients of the tower
+/// `Fq2 = Fq[u]/(u² - β)`, `Fq6 = Fq2[v]/(v³ - ξ)`, `Fq12 = Fq6[w]/(w² - v)`
+/// from the modulus `p` and the non-residues `β` and `ξ`.
+pub fn compute_frobenius_coeff(p: &BigUint, beta: Fq, xi: Fq2) -> FrobeniusCoeffs {
+    let one = BigUint::from(1u64);
+    // (p^i - 1) for every power of the Frobenius we need.
+    let exps = (0..12u32).map(|i| p.pow(i) - &one).collect::<Vec<_>>();
+
+    let fq2_c1 = core::array::from_fn(|i| pow_biguint(beta, &(&exps[i] >> 1)));
+    let fq6_c1 = core::array::from_fn(|i| pow_biguint(xi, &(&exps[i] / 3u64)));
+    let fq6_c2 = core::array::from_fn(|i| pow_biguint(xi, &(&exps[i] * 2u64 / 3u64)));
+    let fq12_c1 = core::array::from_fn(|i| pow_biguint(xi, &(&exps[i] / 6u64)));
+
+    FrobeniusCoeffs {
+        fq2_c1,
+        fq6_c1,
+        fq6_c2,
+        fq12_c1,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bn256::{
+        BN256_P, FROBENIUS_COEFF_FQ12_C1, FROBENIUS_COEFF_FQ6_C1, FROBENIUS_COEFF_FQ6_C2,
+    };
+    use rand_core::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    fn modulus() -> BigUint {
+        BigUint::from_slice(
+            &BN256_P
+                .iter()
+                .flat_map(|limb| [*limb as u32, (*limb >> 32) as u32])
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    #[test]
+    fn test_frobenius_coeffs() {
+        let coeffs =
+            compute_frobenius_coeff(&modulus(), -Fq::one(), Fq2::mul_by_nonresidue_constant());
+
+        assert_eq!(coeffs.fq6_c1, FROBENIUS_COEFF_FQ6_C1);
+        assert_eq!(coeffs.fq6_c2, FROBENIUS_COEFF_FQ6_C2);
+        assert_eq!(coeffs.fq12_c1, FROBENIUS_COEFF_FQ12_C1);
+
+        // `Fq2` has no coefficient table, its Frobenius map conjugates on odd
+        // powers. Check that this agrees with the computed coefficients.
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        for power in 0..4 {
+            let a = Fq2::random(&mut rng);
+            let mut b = a;
+            b.frobenius_map(power);
+            assert_eq!(b.c0, a.c0);
+            assert_eq!(b.c1, a.c1 * coeffs.fq2_c1[power % 2]);
+        }
+    }
+}
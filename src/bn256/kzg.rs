@@ -0,0 +1,114 @@
+//! Pairing checks used by KZG verifiers over BN256.
+
+use crate::bn256::curve::{G1Affine, G2Affine, G1};
+use crate::bn256::engine::{multi_miller_loop, G2Prepared, Gt};
+use crate::group::prime::PrimeCurveAffine;
+use crate::group::{Curve, GroupEncoding};
+use pairing::MillerLoopResult;
+use std::collections::hash_map::{Entry, HashMap};
+
+/// Checks that `e(P_0, Q_0) * e(P_1, Q_1) * ... == expected_product`.
+///
+/// A batch of KZG proofs is usually folded by the caller into a handful of
+/// pairs by scaling each opening with a random challenge, which turns many
+/// independent pairing equations into a single product. This function
+/// performs that final check: pairs sharing the same G2 point are merged
+/// through `e(P, Q) * e(P', Q) = e(P + P', Q)`, every remaining pair goes
+/// through one multi Miller loop, and a single final exponentiation is
+/// applied to the result.
+pub fn batch_verify_pairings(pairs: &[(&G1Affine, &G2Affine)], expected_product: &Gt) -> bool {
+    let (g1s, g2s) = merge_by_g2(pairs);
+
+    let g2s = g2s
+        .into_iter()
+        .map(G2Prepared::from_affine)
+        .collect::<Vec<_>>();
+    let terms = g1s.iter().zip(g2s.iter()).collect::<Vec<_>>();
+
+    multi_miller_loop(&terms).final_exponentiation() == *expected_product
+}
+
+/// Checks that `e(P_0, Q_0) * e(P_1, Q_1) * ... == 1`.
+///
+/// This is the form KZG verification equations take once every term has
+/// been moved to one side.
+pub fn batch_verify_pairings_trivial(pairs: &[(&G1Affine, &G2Affine)]) -> bool {
+    batch_verify_pairings(pairs, &Gt::identity())
+}
+
+/// Sums the G1 points that are paired with the same G2 point, dropping pairs
+/// where either side is the identity since they contribute nothing.
+fn merge_by_g2(pairs: &[(&G1Affine, &G2Affine)]) -> (Vec<G1Affine>, Vec<G2Affine>) {
+    let mut g1s: Vec<G1> = Vec::with_capacity(pairs.len());
+    let mut g2s: Vec<G2Affine> = Vec::with_capacity(pairs.len());
+    // Index into `g1s`/`g2s`, keyed by the compressed encoding of the G2 point
+    let mut index: HashMap<Vec<u8>, usize> = HashMap::with_capacity(pairs.len());
+
+    for (p, q) in pairs.iter() {
+        if bool::from(p.is_identity() | q.is_identity()) {
+            continue;
+        }
+        match index.entry(q.to_bytes().as_ref().to_vec()) {
+            Entry::Occupied(entry) => g1s[*entry.get()] += *p,
+            Entry::Vacant(entry) => {
+                entry.insert(g2s.len());
+                g1s.push(p.to_curve());
+                g2s.push(**q);
+            }
+        }
+    }
+
+    let mut affine = vec![G1Affine::identity(); g1s.len()];
+    G1::batch_normalize(&g1s, &mut affine);
+    (affine, g2s)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bn256::{engine::pairing, Fr, G2};
+    use ff::Field;
+    use group::Group;
+    use rand_core::OsRng;
+
+    #[test]
+    fn test_batch_verify_pairings() {
+        let a = Fr::random(OsRng);
+        let b = Fr::random(OsRng);
+
+        let g1 = G1Affine::generator();
+        let g2 = G2Affine::generator();
+        let a_g1 = (g1 * a).to_affine();
+        let b_g2 = (g2 * b).to_affine();
+        let neg_ab_g1 = (-(g1 * (a * b))).to_affine();
+
+        // e(aG, bH) * e(-abG, H) == 1
+        assert!(batch_verify_pairings_trivial(&[
+            (&a_g1, &b_g2),
+            (&neg_ab_g1, &g2)
+        ]));
+        assert!(!batch_verify_pairings_trivial(&[
+            (&a_g1, &b_g2),
+            (&g1, &g2)
+        ]));
+
+        // e(aG, H) * e(bG, H) == e(G, H)^(a + b), with both terms sharing H
+        let b_g1 = (g1 * b).to_affine();
+        let expected = pairing(&g1, &g2) * (a + b);
+        assert!(batch_verify_pairings(
+            &[(&a_g1, &g2), (&b_g1, &g2)],
+            &expected
+        ));
+        assert!(!batch_verify_pairings(&[(&a_g1, &g2)], &expected));
+
+        // Pairs that cancel out once merged and pairs involving the identity
+        let neg_a_g1 = -a_g1;
+        let identity = G2::identity().to_affine();
+        assert!(batch_verify_pairings_trivial(&[
+            (&a_g1, &b_g2),
+            (&neg_a_g1, &b_g2),
+            (&g1, &identity),
+        ]));
+        assert!(batch_verify_pairings_trivial(&[]));
+    }
+}
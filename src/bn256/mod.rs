@@ -5,17 +5,26 @@ mod fq12;
 mod fq2;
 mod fq6;
 mod fr;
+pub mod kzg;
 
 #[cfg(feature = "asm")]
 mod assembly;
+#[cfg(feature = "dh")]
+mod dh;
+#[cfg(any(test, feature = "frobenius-coeffs"))]
+mod frobenius;
 
 pub use curve::*;
+#[cfg(feature = "dh")]
+pub use dh::*;
 pub use engine::*;
 pub use fq::*;
 pub use fq12::*;
 pub use fq2::*;
 pub use fq6::*;
 pub use fr::*;
+#[cfg(feature = "frobenius-coeffs")]
+pub use frobenius::*;
 
 #[cfg(test)]
 mod test {
@@ -115,7 +115,7 @@ macro_rules! new_curve_impl {
                         $base::from_bytes(&xbytes).and_then(|x| {
                             CtOption::new(Self::identity(), x.is_zero() & (is_inf)).or_else(|| {
                                 $name_affine::y2(x).sqrt().and_then(|y| {
-                                    let sign = Choice::from(y.to_bytes()[0] & 1);
+                                    let sign = ff::PrimeField::is_odd(&y);
 
                                     let y = $base::conditional_select(&y, &-y, ysign ^ sign);
 
@@ -142,7 +142,12 @@ macro_rules! new_curve_impl {
                             [< $name Compressed >](bytes)
                         } else {
                             let (x, y) = (self.x, self.y);
-                            let sign = (y.to_bytes()[0] & 1) << 6;
+                            // The sign is `sgn0(y)` from RFC 9380, i.e.
+                            // `is_odd`. For a prime field base that is the
+                            // parity of y. For an Fq2 base it is the parity of
+                            // y.c0, or of y.c1 when y.c0 is zero, so that y
+                            // and -y always get different signs.
+                            let sign = ff::PrimeField::is_odd(&y).unwrap_u8() << 6;
                             let mut xbytes = [0u8; [< $name _COMPRESSED_SIZE >]];
                             xbytes[..$base::size()].copy_from_slice(&x.to_bytes());
                             xbytes[[< $name _COMPRESSED_SIZE >] - 1] |= sign;
@@ -107,6 +107,26 @@ mod tests {
         crate::tests::curve::curve_tests::<G1>();
     }
 
+    #[test]
+    fn test_bn256_field_casts() {
+        use crate::bn256;
+        use crate::grumpkin::{
+            bn256_fq_as_grumpkin_fr, bn256_fr_as_grumpkin_fq, grumpkin_fq_as_bn256_fr,
+            grumpkin_fr_as_bn256_fq, Fq,
+        };
+
+        let x = bn256::Fr::random(OsRng);
+        let y = bn256::Fq::random(OsRng);
+        assert!(core::ptr::eq(bn256_fr_as_grumpkin_fq(&x), &x));
+        assert!(core::ptr::eq(bn256_fq_as_grumpkin_fr(&y), &y));
+        assert_eq!(grumpkin_fq_as_bn256_fr(bn256_fr_as_grumpkin_fq(&x)), &x);
+        assert_eq!(grumpkin_fr_as_bn256_fq(bn256_fq_as_grumpkin_fr(&y)), &y);
+
+        // The two fields are swapped between the curves
+        assert_eq!(Fq::MODULUS, bn256::Fr::MODULUS);
+        assert_eq!(Fr::MODULUS, bn256::Fq::MODULUS);
+    }
+
     #[test]
     fn test_endo() {
         let z_impl = Fr::ZETA;
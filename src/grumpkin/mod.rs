@@ -2,3 +2,28 @@ mod curve;
 
 pub use crate::bn256::{Fq as Fr, Fr as Fq};
 pub use curve::*;
+
+// Grumpkin and BN256 form a cycle: the base field of each is the scalar field
+// of the other. The fields are re-exported above rather than redefined, so
+// the conversions below are the identity on the same type and involve no
+// copy or transmute.
+
+/// Views a BN256 scalar as a Grumpkin base field element.
+pub const fn bn256_fr_as_grumpkin_fq(x: &crate::bn256::Fr) -> &Fq {
+    x
+}
+
+/// Views a Grumpkin base field element as a BN256 scalar.
+pub const fn grumpkin_fq_as_bn256_fr(x: &Fq) -> &crate::bn256::Fr {
+    x
+}
+
+/// Views a BN256 base field element as a Grumpkin scalar.
+pub const fn bn256_fq_as_grumpkin_fr(x: &crate::bn256::Fq) -> &Fr {
+    x
+}
+
+/// Views a Grumpkin scalar as a BN256 base field element.
+pub const fn grumpkin_fr_as_bn256_fq(x: &Fr) -> &crate::bn256::Fq {
+    x
+}
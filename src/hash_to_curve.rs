@@ -88,6 +88,76 @@ fn hash_to_field<F: FromUniformBytes<64>>(
     }
 }
 
+/// Implementation of `expand_message_xof` from
+/// <https://datatracker.ietf.org/doc/html/rfc9380#name-expand_message_xof>
+/// with SHAKE-128, returning `len` uniformly random bytes.
+///
+/// Domain separation tags longer than 255 bytes are first hashed down as
+/// the RFC prescribes.
+///
+/// # Panics
+///
+/// Panics if `len` is larger than 65535.
+#[cfg(feature = "shake")]
+pub fn expand_message_xof(msg: &[u8], dst: &[u8], len: usize) -> Vec<u8> {
+    use sha3::digest::{ExtendableOutput, Update, XofReader};
+    use sha3::Shake128;
+
+    assert!(len <= u16::MAX as usize);
+
+    let long_dst;
+    let dst = if dst.len() > 255 {
+        // ceil(2 * k / 8) bytes for k = 128
+        let mut hashed = [0u8; 32];
+        Shake128::default()
+            .chain(b"H2C-OVERSIZE-DST-")
+            .chain(dst)
+            .finalize_xof()
+            .read(&mut hashed);
+        long_dst = hashed;
+        &long_dst[..]
+    } else {
+        dst
+    };
+
+    let mut uniform_bytes = vec![0u8; len];
+    Shake128::default()
+        .chain(msg)
+        .chain((len as u16).to_be_bytes())
+        .chain(dst)
+        .chain([dst.len() as u8])
+        .finalize_xof()
+        .read(&mut uniform_bytes);
+    uniform_bytes
+}
+
+/// Hashes `msg` to `count` field elements, expanding it with
+/// [`expand_message_xof`].
+///
+/// As in the BLAKE2b based `hash_to_field` used by the curves, every element
+/// is reduced from 64 bytes (`k = 256`), which is more than the RFC's
+/// `L = 48` for 254-bit fields. Outputs therefore do not match suites built
+/// on `L = 48`.
+///
+/// # Panics
+///
+/// Panics if `count` is larger than 1023, since `expand_message_xof` can
+/// produce at most 65535 bytes.
+#[cfg(feature = "shake")]
+pub fn hash_to_field_xof<F: FromUniformBytes<64>>(msg: &[u8], dst: &[u8], count: usize) -> Vec<F> {
+    const CHUNKLEN: usize = 64;
+
+    expand_message_xof(msg, dst, count * CHUNKLEN)
+        .chunks(CHUNKLEN)
+        .map(|chunk| {
+            let mut little = [0u8; CHUNKLEN];
+            little.copy_from_slice(chunk);
+            little.reverse();
+            F::from_uniform_bytes(&little)
+        })
+        .collect()
+}
+
 // Implementation of <https://datatracker.ietf.org/doc/html/rfc9380#name-simplified-swu-method>
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn sswu_map_to_curve<C>(u: C::Base, z: C::Base) -> C
@@ -368,3 +438,121 @@ pub(crate) fn svdw_precomputed_constants<C: CurveExt>(z: C::Base) -> [C::Base; 4
 
     [c1, c2, c3, c4]
 }
+
+#[cfg(all(test, feature = "shake"))]
+mod test {
+    use super::{expand_message_xof, hash_to_field_xof};
+    use crate::bn256::Fr;
+    use ff::PrimeField;
+
+    // From <https://datatracker.ietf.org/doc/html/rfc9380#name-expand_message_xofshake128>
+    #[test]
+    fn test_expand_message_xof() {
+        let dst = b"QUUX-V01-CS02-with-expander-SHAKE128";
+        let q128 = format!("q128_{}", "q".repeat(128));
+        let a512 = format!("a512_{}", "a".repeat(512));
+
+        for (msg, len, uniform_bytes) in [
+            (
+                "",
+                0x20,
+                "86518c9cd86581486e9485aa74ab35ba150d1c75c88e26b7043e44e2acd735a2",
+            ),
+            (
+                "abc",
+                0x20,
+                "8696af52a4d862417c0763556073f47bc9b9ba43c99b505305cb1ec04a9ab468",
+            ),
+            (
+                "abcdef0123456789",
+                0x20,
+                "912c58deac4821c3509dbefa094df54b34b8f5d01a191d1d3108a2c89077acca",
+            ),
+            (
+                &q128,
+                0x20,
+                "1adbcc448aef2a0cebc71dac9f756b22e51839d348e031e63b33ebb50faeaf3f",
+            ),
+            (
+                &a512,
+                0x20,
+                "df3447cc5f3e9a77da10f819218ddf31342c310778e0e4ef72bbaecee786a4fe",
+            ),
+            (
+                "",
+                0x80,
+                "7314ff1a155a2fb99a0171dc71b89ab6e3b2b7d59e38e64419b8b6294d03ffee\
+                 42491f11370261f436220ef787f8f76f5b26bdcd850071920ce023f3ac468477\
+                 44f4612b8714db8f5db83205b2e625d95afd7d7b4d3094d3bdde815f52850bb4\
+                 1ead9822e08f22cf41d615a303b0d9dde73263c049a7b9898208003a739a2e57",
+            ),
+            (
+                "abc",
+                0x80,
+                "c952f0c8e529ca8824acc6a4cab0e782fc3648c563ddb00da7399f2ae35654f4\
+                 860ec671db2356ba7baa55a34a9d7f79197b60ddae6e64768a37d699a7832349\
+                 6db3878c8d64d909d0f8a7de4927dcab0d3dbbc26cb20a49eceb0530b431cdf4\
+                 7bc8c0fa3e0d88f53b318b6739fbed7d7634974f1b5c386d6230c76260d5337a",
+            ),
+            (
+                &a512,
+                0x80,
+                "9d763a5ce58f65c91531b4100c7266d479a5d9777ba761693d052acd37d149e7\
+                 ac91c796a10b919cd74a591a1e38719fb91b7203e2af31eac3bff7ead2c195af\
+                 7d88b8bc0a8adf3d1e90ab9bed6ddc2b7f655dd86c730bdeaea884e737410971\
+                 42c92f0e3fc1811b699ba593c7fbd81da288a29d423df831652e3a01a9374999",
+            ),
+        ] {
+            assert_eq!(
+                hex::encode(expand_message_xof(msg.as_bytes(), dst, len)),
+                uniform_bytes
+            );
+        }
+
+        // DST longer than 255 bytes
+        let dst = format!(
+            "QUUX-V01-CS02-with-expander-SHAKE128-long-DST-{}",
+            "1".repeat(210)
+        );
+        for (msg, uniform_bytes) in [
+            (
+                "",
+                "827c6216330a122352312bccc0c8d6e7a146c5257a776dbd9ad9d75cd880fc53",
+            ),
+            (
+                "abc",
+                "690c8d82c7213b4282c6cb41c00e31ea1d3e2005f93ad19bbf6da40f15790c5c",
+            ),
+        ] {
+            assert_eq!(
+                hex::encode(expand_message_xof(msg.as_bytes(), dst.as_bytes(), 0x20)),
+                uniform_bytes
+            );
+        }
+    }
+
+    #[test]
+    fn test_hash_to_field_xof() {
+        let us = hash_to_field_xof::<Fr>(b"abc", b"halo2curves-test-SHAKE128", 2);
+        assert_eq!(
+            us,
+            [
+                Fr::from_str_vartime(
+                    "13285283688853167619091841812299161240737384272716776297595557274272624631366"
+                )
+                .unwrap(),
+                Fr::from_str_vartime(
+                    "7781355225369236891462816047947599371888921858890683063719557587336261458099"
+                )
+                .unwrap(),
+            ]
+        );
+        assert!(hash_to_field_xof::<Fr>(b"abc", b"dst", 0).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_hash_to_field_xof_too_long() {
+        hash_to_field_xof::<Fr>(b"abc", b"dst", 1024);
+    }
+}
@@ -6,6 +6,7 @@ pub mod msm;
 pub mod multicore;
 pub mod serde;
 
+pub mod bls12381;
 pub mod bn256;
 pub mod grumpkin;
 pub mod pasta;
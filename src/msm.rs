@@ -50,16 +50,64 @@ fn get_booth_index(window_index: usize, window_size: usize, el: &[u8]) -> i32 {
     }
 }
 
-pub fn multiexp_serial<C: CurveAffine>(coeffs: &[C::Scalar], bases: &[C], acc: &mut C::Curve) {
-    let coeffs: Vec<_> = coeffs.iter().map(|a| a.to_repr()).collect();
+/// Returns the width-`window` non-adjacent form of the little-endian integer
+/// `el`, least significant digit first.
+///
+/// Every nonzero digit is odd, lies in `(-2^(window - 1), 2^(window - 1))`
+/// and is followed by at least `window - 1` zero digits.
+pub(crate) fn wnaf_form(el: &[u8], window: usize) -> Vec<i64> {
+    assert!((2..=62).contains(&window));
+
+    let bit = |pos: usize| {
+        el.get(pos / 8)
+            .map_or(0, |byte| ((byte >> (pos % 8)) & 1) as u64)
+    };
+    let width = 1u64 << window;
+    let bit_len = el.len() * 8;
+
+    let mut wnaf = Vec::with_capacity(bit_len + 1);
+    let mut carry = 0;
+    let mut pos = 0;
+    while pos < bit_len {
+        let window_val = carry + (0..window).fold(0, |acc, i| acc | (bit(pos + i) << i));
+
+        if window_val & 1 == 0 {
+            // the carry, if any, is propagated to the next position
+            wnaf.push(0);
+            pos += 1;
+        } else {
+            if window_val < width / 2 {
+                carry = 0;
+                wnaf.push(window_val as i64);
+            } else {
+                carry = 1;
+                wnaf.push(window_val as i64 - width as i64);
+            }
+            wnaf.extend(std::iter::repeat(0).take(window - 1));
+            pos += window;
+        }
+    }
+    if carry == 1 {
+        wnaf.push(1);
+    }
+    wnaf
+}
 
-    let c = if bases.len() < 4 {
+/// Window size used by the serial bucket method for `n` bases.
+fn window_size(n: usize) -> usize {
+    if n < 4 {
         1
-    } else if bases.len() < 32 {
+    } else if n < 32 {
         3
     } else {
-        (f64::from(bases.len() as u32)).ln().ceil() as usize
-    };
+        (f64::from(n as u32)).ln().ceil() as usize
+    }
+}
+
+pub fn multiexp_serial<C: CurveAffine>(coeffs: &[C::Scalar], bases: &[C], acc: &mut C::Curve) {
+    let coeffs: Vec<_> = coeffs.iter().map(|a| a.to_repr()).collect();
+
+    let c = window_size(bases.len());
 
     let number_of_windows = C::Scalar::NUM_BITS as usize / c + 1;
 
@@ -123,6 +171,64 @@ pub fn multiexp_serial<C: CurveAffine>(coeffs: &[C::Scalar], bases: &[C], acc: &
     }
 }
 
+/// Computes `sum_i coeffs[i] * bases[i]` on the current thread, keeping the
+/// buckets in `scratch` instead of allocating them.
+///
+/// The window size is the one [`multiexp_serial`] would use, lowered
+/// if needed so that its `2^(c - 1)` buckets fit in `scratch`. As in
+/// [`multiexp_serial`], the scalars are converted out of Montgomery form once
+/// up front, which is the only allocation.
+///
+/// # Panics
+///
+/// Panics if `coeffs` and `bases` have different lengths, or if `scratch`
+/// is empty while `bases` is not.
+pub fn multiexp_serial_into<C: CurveAffine>(
+    coeffs: &[C::Scalar],
+    bases: &[C],
+    scratch: &mut [C::Curve],
+) -> C::Curve {
+    assert_eq!(coeffs.len(), bases.len());
+    let mut acc = C::Curve::identity();
+    if bases.is_empty() {
+        return acc;
+    }
+    assert!(!scratch.is_empty(), "empty MSM scratch buffer");
+
+    let coeffs: Vec<_> = coeffs.iter().map(|a| a.to_repr()).collect();
+
+    let c = window_size(bases.len()).min(scratch.len().ilog2() as usize + 1);
+    let buckets = &mut scratch[..1 << (c - 1)];
+
+    let number_of_windows = C::Scalar::NUM_BITS as usize / c + 1;
+
+    for current_window in (0..number_of_windows).rev() {
+        for _ in 0..c {
+            acc = acc.double();
+        }
+
+        buckets.fill(C::Curve::identity());
+        for (coeff, base) in coeffs.iter().zip(bases.iter()) {
+            let coeff = get_booth_index(current_window, c, coeff.as_ref());
+            if coeff.is_positive() {
+                buckets[coeff as usize - 1] += base;
+            }
+            if coeff.is_negative() {
+                buckets[coeff.unsigned_abs() as usize - 1] -= base;
+            }
+        }
+
+        // Summation by parts
+        let mut running_sum = C::Curve::identity();
+        for bucket in buckets.iter().rev() {
+            running_sum += bucket;
+            acc += &running_sum;
+        }
+    }
+
+    acc
+}
+
 /// Performs a small multi-exponentiation operation.
 /// Uses the double-and-add algorithm with doublings shared across points.
 pub fn small_multiexp<C: CurveAffine>(coeffs: &[C::Scalar], bases: &[C]) -> C::Curve {
@@ -181,6 +287,34 @@ pub fn best_multiexp<C: CurveAffine>(coeffs: &[C::Scalar], bases: &[C]) -> C::Cu
     }
 }
 
+/// A multi-scalar multiplication backend, computing `sum_i scalars[i] * bases[i]`.
+///
+/// Code that is generic over `M: Msm<C, C::Scalar>` can swap in another
+/// implementation (e.g. one running on a GPU) without depending on it.
+pub trait Msm<C: CurveAffine, F: PrimeField> {
+    /// Computes `sum_i scalars[i] * bases[i]`.
+    ///
+    /// Implementations may panic if `bases` and `scalars` have different
+    /// lengths.
+    fn msm(bases: &[C], scalars: &[F]) -> C::Curve;
+}
+
+/// Marker for [`Msm`] backends that accept arbitrary bases, as opposed to
+/// ones working from tables precomputed for a fixed set of bases.
+pub trait VariableBaseMsm<C: CurveAffine, F: PrimeField>: Msm<C, F> {}
+
+/// The [`Msm`] backend of this crate, running [`best_multiexp`] on the CPU.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CpuMsm;
+
+impl<C: CurveAffine> Msm<C, C::Scalar> for CpuMsm {
+    fn msm(bases: &[C], scalars: &[C::Scalar]) -> C::Curve {
+        best_multiexp(scalars, bases)
+    }
+}
+
+impl<C: CurveAffine> VariableBaseMsm<C, C::Scalar> for CpuMsm {}
+
 #[cfg(test)]
 mod test {
 
@@ -363,6 +497,55 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_wnaf_form() {
+        for window in 2..8 {
+            for _ in 0..100 {
+                let scalar = Fr::random(OsRng);
+                let wnaf = super::wnaf_form(scalar.to_repr().as_ref(), window);
+
+                let mut acc = Fr::zero();
+                for digit in wnaf.iter().rev() {
+                    acc = acc.double();
+                    assert!(digit.unsigned_abs() < 1 << (window - 1));
+                    let abs = Fr::from(digit.unsigned_abs());
+                    acc += if *digit < 0 { -abs } else { abs };
+                }
+                assert_eq!(acc, scalar);
+
+                for (i, digit) in wnaf.iter().enumerate() {
+                    if *digit != 0 {
+                        assert_eq!(digit & 1, 1);
+                        assert!(wnaf[i + 1..].iter().take(window - 1).all(|d| *d == 0));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_msm_trait() {
+        use super::{CpuMsm, Msm, VariableBaseMsm};
+
+        fn commit<C: CurveAffine, M: VariableBaseMsm<C, C::Scalar>>(
+            bases: &[C],
+            scalars: &[C::Scalar],
+        ) -> C::Curve {
+            M::msm(bases, scalars)
+        }
+
+        let bases: Vec<G1Affine> = (0..50).map(|_| G1Affine::random(OsRng)).collect();
+        let scalars: Vec<Fr> = (0..50).map(|_| Fr::random(OsRng)).collect();
+        let expected = bases
+            .iter()
+            .zip(scalars.iter())
+            .fold(G1::identity(), |acc, (base, scalar)| acc + base * scalar);
+
+        assert_eq!(commit::<_, CpuMsm>(&bases, &scalars), expected);
+        assert_eq!(<CpuMsm as Msm<_, _>>::msm(&bases, &scalars), expected);
+        assert_eq!(CpuMsm::msm(&bases[..0], &scalars[..0]), G1::identity());
+    }
+
     fn run_msm_cross<C: CurveAffine>(min_k: usize, max_k: usize) {
         let points = (0..1 << max_k)
             .map(|_| C::Curve::random(OsRng))
@@ -483,8 +483,10 @@ impl PrimeField for Fp2 {
         Fp2Bytes(self.to_bytes())
     }
 
+    /// Returns `sgn0(self)` as defined in RFC 9380: the parity of `c0`, or
+    /// the parity of `c1` when `c0` is zero.
     fn is_odd(&self) -> Choice {
-        Choice::from(self.to_repr().as_ref()[0] & 1)
+        self.c0.is_odd() | (self.c0.is_zero() & self.c1.is_odd())
     }
 }
 
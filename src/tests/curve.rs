@@ -58,6 +58,26 @@ fn serdes<G: CurveExt>() {
     }
 }
 
+/// Checks that the compressed encoding of `n` random points, and of their
+/// negations and of the identity, decodes back to the same point.
+pub fn compressed_roundtrip_test<G: CurveExt>(n: usize) {
+    let identity = G::AffineExt::identity();
+    assert_eq!(
+        G::AffineExt::from_bytes(&identity.to_bytes()).unwrap(),
+        identity
+    );
+    for _ in 0..n {
+        let point: G::AffineExt = G::random(OsRng).into();
+        for point in [point, -point] {
+            assert_eq!(G::AffineExt::from_bytes(&point.to_bytes()).unwrap(), point);
+            assert_eq!(
+                G::from_bytes(&point.to_curve().to_bytes()).unwrap(),
+                point.to_curve()
+            );
+        }
+    }
+}
+
 #[cfg(feature = "derive_serde")]
 pub fn random_serde_test<G>()
 where
@@ -307,3 +307,29 @@ pub fn random_quadratic_residue_test<F: Field + Legendre>() {
         assert_eq!(!is_quad_non_res, is_quad_res_or_zero)
     }
 }
+
+/// Checks that `frobenius_map(1)` applied `i` times agrees with
+/// `frobenius_map(i)`, and that the map has exactly the given order.
+pub fn frobenius_order_test<F: Field>(order: usize, frobenius_map: impl Fn(&mut F, usize)) {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+    for _ in 0..100 {
+        let a = F::random(&mut rng);
+        let mut b = a;
+        for i in 1..order {
+            frobenius_map(&mut b, 1);
+            let mut c = a;
+            frobenius_map(&mut c, i);
+            assert_eq!(b, c);
+        }
+        assert_ne!(b, a);
+        frobenius_map(&mut b, 1);
+        assert_eq!(b, a);
+
+        let mut c = a;
+        frobenius_map(&mut c, order);
+        assert_eq!(c, a);
+    }
+}
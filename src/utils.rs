@@ -1,9 +1,11 @@
-use ff::PrimeField;
-use num_bigint::BigUint;
-use num_traits::Num;
-use std::borrow::Cow;
+/// Parses a field element from either a `0x`-prefixed hex string or a decimal
+/// string. Panics if the string is malformed or not a canonical element.
+#[cfg(test)]
+pub(crate) fn fe_from_str<F: ff::PrimeField>(string: impl AsRef<str>) -> F {
+    use num_bigint::BigUint;
+    use num_traits::Num;
+    use std::borrow::Cow;
 
-pub(crate) fn fe_from_str<F: PrimeField>(string: impl AsRef<str>) -> F {
     let string = string.as_ref();
     let oct = if let Some(hex) = string.strip_prefix("0x") {
         Cow::Owned(BigUint::from_str_radix(hex, 16).unwrap().to_string())
@@ -12,3 +14,107 @@ pub(crate) fn fe_from_str<F: PrimeField>(string: impl AsRef<str>) -> F {
     };
     F::from_str_vartime(&oct).unwrap()
 }
+
+/// Parses a big-endian hex string, with or without a `0x` prefix, into
+/// little-endian `u64` limbs at compile time.
+///
+/// Panics, which is a compile error in const context, on characters that are
+/// not hex digits or if the value does not fit in `N` limbs.
+pub const fn limbs_from_hex<const N: usize>(hex: &str) -> [u64; N] {
+    let bytes = hex.as_bytes();
+    let start = if bytes.len() >= 2 && bytes[0] == b'0' && (bytes[1] == b'x' || bytes[1] == b'X') {
+        2
+    } else {
+        0
+    };
+    assert!(bytes.len() > start, "empty hex string");
+
+    let mut limbs = [0u64; N];
+    let mut i = bytes.len();
+    let mut digit = 0;
+    while i > start {
+        i -= 1;
+        let nibble = match bytes[i] {
+            b'0'..=b'9' => bytes[i] - b'0',
+            b'a'..=b'f' => bytes[i] - b'a' + 10,
+            b'A'..=b'F' => bytes[i] - b'A' + 10,
+            _ => panic!("invalid hex digit"),
+        } as u64;
+        if digit >= N * 16 {
+            assert!(nibble == 0, "hex value does not fit in the limbs");
+        } else {
+            limbs[digit / 16] |= nibble << ((digit % 16) * 4);
+        }
+        digit += 1;
+    }
+    limbs
+}
+
+/// Builds a BN256 [`Fq`](crate::bn256::Fq) from a hex string literal in
+/// canonical (not Montgomery) form. Usable in `const` items.
+///
+/// ```
+/// use halo2curves::{bn256::Fq, fq_from_hex};
+///
+/// const TWO: Fq = fq_from_hex!("0x02");
+/// assert_eq!(TWO, Fq::from(2));
+/// ```
+#[macro_export]
+macro_rules! fq_from_hex {
+    ($hex:expr) => {
+        $crate::bn256::Fq::from_raw($crate::utils::limbs_from_hex::<4>($hex))
+    };
+}
+
+/// Builds a BN256 [`Fr`](crate::bn256::Fr) from a hex string literal in
+/// canonical (not Montgomery) form. Usable in `const` items.
+///
+/// ```
+/// use halo2curves::{bn256::Fr, fr_from_hex};
+///
+/// const TWO: Fr = fr_from_hex!("0x02");
+/// assert_eq!(TWO, Fr::from(2));
+/// ```
+#[macro_export]
+macro_rules! fr_from_hex {
+    ($hex:expr) => {
+        $crate::bn256::Fr::from_raw($crate::utils::limbs_from_hex::<4>($hex))
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bn256::{Fq, Fr};
+    use ff::Field;
+
+    #[test]
+    fn test_from_hex_macros() {
+        const Q_MINUS_ONE: Fq =
+            fq_from_hex!("0x30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd46");
+        const R_MINUS_ONE: Fr =
+            fr_from_hex!("30644E72E131A029B85045B68181585D2833E84879B9709143E1F593F0000000");
+        assert_eq!(Q_MINUS_ONE, -Fq::ONE);
+        assert_eq!(R_MINUS_ONE, -Fr::ONE);
+
+        let hex = "0x2f058acc133957074ac79e9b9b1867a0cf3d13df7aa7de7f48e9a6be7d96aa6d";
+        assert_eq!(fq_from_hex!(hex), fe_from_str::<Fq>(hex));
+        assert_eq!(fr_from_hex!("0x0"), Fr::ZERO);
+        assert_eq!(
+            limbs_from_hex::<2>("0x0000000000000001ffffffffffffffff"),
+            [u64::MAX, 1]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_limbs_from_hex_overflow() {
+        limbs_from_hex::<1>("0x10000000000000000");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_limbs_from_hex_invalid_digit() {
+        limbs_from_hex::<4>("0x12g4");
+    }
+}
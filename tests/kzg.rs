@@ -0,0 +1,80 @@
+//! End-to-end KZG commit, open and verify over BN256.
+
+use halo2curves::bn256::kzg::batch_verify_pairings_trivial;
+use halo2curves::bn256::{Fr, G1Affine, G2Affine, G1, G2};
+use halo2curves::ff::Field;
+use halo2curves::group::{prime::PrimeCurveAffine, Curve};
+use halo2curves::msm::best_multiexp;
+use rand_core::OsRng;
+
+const DEGREE: usize = 100;
+
+struct Srs {
+    g1: Vec<G1Affine>,
+    g2: G2Affine,
+    tau_g2: G2Affine,
+}
+
+impl Srs {
+    fn setup(degree: usize, tau: Fr) -> Self {
+        let powers = std::iter::successors(Some(Fr::one()), |p| Some(p * tau))
+            .take(degree + 1)
+            .map(|p| G1Affine::generator() * p)
+            .collect::<Vec<G1>>();
+        let mut g1 = vec![G1Affine::identity(); powers.len()];
+        G1::batch_normalize(&powers, &mut g1);
+
+        Srs {
+            g1,
+            g2: G2Affine::generator(),
+            tau_g2: (G2::generator() * tau).to_affine(),
+        }
+    }
+
+    fn commit(&self, poly: &[Fr]) -> G1Affine {
+        best_multiexp(poly, &self.g1[..poly.len()]).to_affine()
+    }
+
+    /// Returns `p(z)` and a commitment to `(p(X) - p(z)) / (X - z)`.
+    fn open(&self, poly: &[Fr], z: Fr) -> (Fr, G1Affine) {
+        // Synthetic division by (X - z); the final carry is the remainder p(z).
+        let mut quotient = vec![Fr::zero(); poly.len() - 1];
+        let mut carry = Fr::zero();
+        for (i, c) in poly.iter().enumerate().rev() {
+            carry = carry * z + c;
+            if i > 0 {
+                quotient[i - 1] = carry;
+            }
+        }
+        (carry, self.commit(&quotient))
+    }
+
+    /// Checks `e(C - [y]G1 + [z]pi, G2) * e(-pi, [tau]G2) == 1`.
+    fn verify(&self, commitment: &G1Affine, z: Fr, y: Fr, proof: &G1Affine) -> bool {
+        let lhs = (commitment - G1Affine::generator() * y + proof * z).to_affine();
+        let neg_proof = -proof;
+        batch_verify_pairings_trivial(&[(&lhs, &self.g2), (&neg_proof, &self.tau_g2)])
+    }
+}
+
+fn eval(poly: &[Fr], x: Fr) -> Fr {
+    poly.iter().rev().fold(Fr::zero(), |acc, c| acc * x + c)
+}
+
+#[test]
+fn test_kzg_commit_open_verify() {
+    let srs = Srs::setup(DEGREE, Fr::random(OsRng));
+
+    for _ in 0..10 {
+        let poly = (0..=DEGREE).map(|_| Fr::random(OsRng)).collect::<Vec<_>>();
+        let commitment = srs.commit(&poly);
+
+        let z = Fr::random(OsRng);
+        let (y, proof) = srs.open(&poly, z);
+        assert_eq!(y, eval(&poly, z));
+        assert!(srs.verify(&commitment, z, y, &proof));
+
+        assert!(!srs.verify(&commitment, z, y + Fr::one(), &proof));
+        assert!(!srs.verify(&commitment, z + Fr::one(), y, &proof));
+    }
+}